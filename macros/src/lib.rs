@@ -1,9 +1,16 @@
 use proc_macro::TokenStream;
 
 mod memoize;
+mod scan;
 use crate::memoize::memoize_impl;
+use crate::scan::scan_impl;
 
 #[proc_macro_attribute]
 pub fn memoize(attr: TokenStream, item: TokenStream) -> TokenStream {
     memoize_impl(attr, item)
 }
+
+#[proc_macro]
+pub fn scan(item: TokenStream) -> TokenStream {
+    scan_impl(item)
+}
@@ -0,0 +1,161 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse::*, *};
+
+struct ScanInput {
+    input: Expr,
+    format: LitStr,
+}
+
+impl Parse for ScanInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let expr = input.parse::<Expr>()?;
+        input.parse::<Token![,]>()?;
+        let format = input.parse::<LitStr>()?;
+        input.parse::<Option<Token![,]>>()?;
+
+        Ok(ScanInput { input: expr, format })
+    }
+}
+
+enum Segment {
+    Literal(String),
+    Capture(Option<Type>),
+}
+
+fn parse_format(format: &LitStr) -> syn::Result<Vec<Segment>> {
+    let value = format.value();
+    let mut segments = vec![];
+    let mut literal = String::new();
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut ty = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => ty.push(c),
+                        None => return Err(Error::new(format.span(), "Unclosed `{` in format")),
+                    }
+                }
+
+                if let Some(Segment::Capture(_)) = segments.last() {
+                    if literal.is_empty() {
+                        return Err(Error::new(
+                            format.span(),
+                            "Captures must be separated by a literal",
+                        ));
+                    }
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+
+                let ty = ty.trim();
+                let ty = if ty.is_empty() {
+                    None
+                } else {
+                    Some(syn::parse_str::<Type>(ty).map_err(|e| Error::new(format.span(), e))?)
+                };
+                segments.push(Segment::Capture(ty));
+            }
+            '}' => return Err(Error::new(format.span(), "Unmatched `}` in format")),
+            c => literal.push(c),
+        }
+    }
+
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+
+    Ok(segments)
+}
+
+pub fn scan_impl(item: TokenStream) -> TokenStream {
+    let ScanInput { input, format } = parse_macro_input!(item as ScanInput);
+
+    let segments = match parse_format(&format) {
+        Ok(segments) => segments,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let mut steps = vec![];
+    let mut values = vec![];
+
+    for (i, segment) in segments.iter().enumerate() {
+        match segment {
+            Segment::Literal(literal) => {
+                steps.push(quote! {
+                    if let Err(e) = advent_of_code::majcn::scan::literal(__input, &mut __pos, #literal) {
+                        break '__scan Err(e);
+                    }
+                });
+            }
+            Segment::Capture(ty) => {
+                let field = values.len();
+                let raw = Ident::new(&format!("__raw_{field}"), format.span());
+
+                let take = match segments.get(i + 1) {
+                    Some(Segment::Literal(delimiter)) => quote! {
+                        advent_of_code::majcn::scan::until(__input, &mut __pos, #delimiter)
+                    },
+                    _ => quote! {
+                        Ok(advent_of_code::majcn::scan::rest(__input, &mut __pos))
+                    },
+                };
+                steps.push(quote! {
+                    let #raw = match #take {
+                        Ok(raw) => raw,
+                        Err(e) => break '__scan Err(e),
+                    };
+                });
+
+                let value = match ty {
+                    Some(ty) => quote! {
+                        advent_of_code::majcn::scan::value::<#ty>(#field, #raw)
+                    },
+                    None => quote! { advent_of_code::majcn::scan::value(#field, #raw) },
+                };
+                values.push(quote! {
+                    match #value {
+                        Ok(value) => value,
+                        Err(e) => break '__scan Err(e),
+                    }
+                });
+            }
+        }
+    }
+
+    let result = if values.len() == 1 {
+        let value = &values[0];
+        quote! { #value }
+    } else {
+        quote! { (#(#values),*) }
+    };
+
+    quote!(
+        '__scan: {
+            let __input: &str = &*(#input);
+            let mut __pos = 0usize;
+
+            #(#steps)*
+
+            if let Err(e) = advent_of_code::majcn::scan::end(__input, __pos) {
+                break '__scan Err(e);
+            }
+
+            Ok(#result)
+        }
+    )
+    .into()
+}
@@ -1,11 +1,12 @@
 advent_of_code::solution!(2);
 
+use advent_of_code::majcn::interval_set::*;
 use advent_of_code::majcn::math::*;
 use advent_of_code::maneatingape::iter::*;
 use advent_of_code::maneatingape::parse::*;
 
-fn parse_data(input: &str) -> Vec<[u64; 2]> {
-    input.iter_unsigned().chunk::<2>().collect()
+fn parse_data(input: &str) -> IntervalSet<u64> {
+    input.iter_unsigned().chunk::<2>().map(|[min_id, max_id]| min_id..=max_id).collect()
 }
 
 fn generate_invalid_ids<const LIMIT: usize>(min_id: u64, max_id: u64) -> Vec<u64> {
@@ -35,10 +36,10 @@ fn generate_invalid_ids<const LIMIT: usize>(min_id: u64, max_id: u64) -> Vec<u64
     result
 }
 
-fn part_x<const LIMIT: usize>(data: Vec<[u64; 2]>) -> u64 {
+fn part_x<const LIMIT: usize>(data: IntervalSet<u64>) -> u64 {
     let mut result = data
-        .into_iter()
-        .flat_map(|[min_id, max_id]| generate_invalid_ids::<LIMIT>(min_id, max_id))
+        .iter()
+        .flat_map(|ids| generate_invalid_ids::<LIMIT>(ids.start, ids.end - 1))
         .collect::<Vec<_>>();
 
     result.sort_unstable();
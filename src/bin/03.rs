@@ -1,7 +1,9 @@
 advent_of_code::solution!(3);
 
+use advent_of_code::maneatingape::parse::*;
+
 fn parse_data(input: &str) -> Vec<Vec<u8>> {
-    input.lines().map(|line| line.bytes().map(|x| x - b'0').collect()).collect()
+    input.lines().map(|line| line.iter_fixed(1).collect()).collect()
 }
 
 fn part_x<const COUNT: usize>(bank: &[u8]) -> u64 {
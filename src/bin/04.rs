@@ -2,7 +2,6 @@ advent_of_code::solution!(4);
 
 use advent_of_code::majcn::grid::*;
 use advent_of_code::maneatingape::grid::*;
-use advent_of_code::maneatingape::point::*;
 
 fn parse_data(input: &str) -> Grid<u8> {
     Grid::parse(input)
@@ -13,11 +12,8 @@ fn part_x(grid: &mut Grid<u8>, remove_after_find: bool) -> u32 {
 
     for point in grid.points() {
         if grid[point] == b'@' {
-            let valid_neighburs = DIAGONAL
-                .map(|d| d + point)
-                .into_iter()
-                .filter(|&n| grid.contains(n) && grid[n] == b'@')
-                .count();
+            let valid_neighburs =
+                grid.diagonal_neighbors(point).filter(|&n| grid[n] == b'@').count();
 
             if valid_neighburs < 4 {
                 result += 1;
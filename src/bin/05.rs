@@ -1,69 +1,40 @@
 advent_of_code::solution!(5);
 
+use advent_of_code::majcn::input::*;
+use advent_of_code::majcn::interval_set::*;
 use advent_of_code::maneatingape::iter::*;
-use advent_of_code::maneatingape::parse::*;
 
-struct Range {
-    start: u64,
-    end: u64,
-}
-
-fn parse_data(input: &str) -> (Vec<Range>, Vec<u64>) {
-    let (fresh_ingredient_ranges_str, available_ingredients_str) =
-        input.split_once("\n\n").unwrap();
+fn parse_data(input: &str) -> (IntervalSet<u64>, Vec<u64>) {
+    let input = Input::new(input);
 
-    let fresh_ingredient_ranges = fresh_ingredient_ranges_str
+    let fresh_ingredient_ranges = input
+        .section(0)
+        .unwrap()
         .iter_unsigned()
         .chunk::<2>()
-        .map(|[start, end]| Range { start, end })
+        .map(|[start, end]| start..=end)
         .collect();
 
-    let available_ingredients = available_ingredients_str.iter_unsigned().collect();
+    let available_ingredients = input.section(1).unwrap().iter_unsigned().collect();
 
     (fresh_ingredient_ranges, available_ingredients)
 }
 
-fn merge_all_ranges(fresh_ingredient_ranges: &mut Vec<Range>) {
-    fresh_ingredient_ranges.sort_unstable_by_key(|r| r.start);
-
-    let mut merged: Vec<Range> = Vec::with_capacity(fresh_ingredient_ranges.len());
-    merged.push(Range {
-        start: fresh_ingredient_ranges[0].start,
-        end: fresh_ingredient_ranges[0].end,
-    });
-
-    for range in fresh_ingredient_ranges.iter().skip(1) {
-        let last = merged.last_mut().unwrap();
-        if last.end >= range.start {
-            last.end = u64::max(last.end, range.end);
-        } else {
-            merged.push(Range { start: range.start, end: range.end });
-        }
-    }
-
-    *fresh_ingredient_ranges = merged;
-}
-
 pub fn part_one(input: &str) -> Option<u32> {
-    let (mut fresh_ingredient_ranges, available_ingredients) = parse_data(input);
-    merge_all_ranges(&mut fresh_ingredient_ranges);
+    let (fresh_ingredient_ranges, available_ingredients) = parse_data(input);
 
     let result = available_ingredients
         .into_iter()
-        .filter(|&ingredient| {
-            let idx = fresh_ingredient_ranges.partition_point(|r| ingredient >= r.start);
-            idx > 0 && ingredient <= fresh_ingredient_ranges[idx - 1].end
-        })
+        .filter(|&ingredient| fresh_ingredient_ranges.contains(ingredient))
         .count() as u32;
 
     Some(result)
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let (mut fresh_ingredient_ranges, _) = parse_data(input);
-    merge_all_ranges(&mut fresh_ingredient_ranges);
+    let (fresh_ingredient_ranges, _) = parse_data(input);
 
-    let result = fresh_ingredient_ranges.into_iter().map(|r| r.end - r.start + 1).sum();
+    let result = fresh_ingredient_ranges.len();
 
     Some(result)
 }
@@ -1,19 +1,20 @@
 advent_of_code::solution!(6);
 
+use advent_of_code::maneatingape::grid::*;
 use advent_of_code::maneatingape::parse::*;
 
-fn parse_data(input: &str) -> (Vec<&str>, Vec<u8>) {
-    let mut lines = input.lines().collect::<Vec<_>>();
-    let operators = lines.pop().unwrap().bytes().filter(|x| !x.is_ascii_whitespace()).collect();
+fn parse_data(input: &str) -> (&str, Vec<u8>) {
+    let (numbers, operators) = input.trim_end_matches('\n').rsplit_once('\n').unwrap();
+    let operators = operators.bytes().filter(|x| !x.is_ascii_whitespace()).collect();
 
-    (lines, operators)
+    (numbers, operators)
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let (lines, operators) = parse_data(input);
+    let (numbers, operators) = parse_data(input);
 
-    let numbers = lines
-        .into_iter()
+    let numbers = numbers
+        .lines()
         .map(|line| line.iter_unsigned::<u64>().collect::<Vec<_>>())
         .collect::<Vec<_>>();
 
@@ -31,35 +32,21 @@ pub fn part_one(input: &str) -> Option<u64> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let (lines, operators) = parse_data(input);
-
-    let lines = lines.into_iter().map(|line| line.bytes().collect::<Vec<_>>()).collect::<Vec<_>>();
+    let (numbers, operators) = parse_data(input);
 
-    let mut numbers = vec![];
-    let mut column_i = 0;
-    let columns_len = lines[0].len();
+    let grid = Grid::parse(numbers);
+    let mut columns = grid.columns().map(|column| {
+        column.filter(|x| x.is_ascii_digit()).fold(0, |acc, d| acc * 10 + (d - b'0') as u64)
+    });
 
     let mut result = 0;
 
     for operator in operators {
-        while column_i < columns_len {
-            let number = lines
-                .iter()
-                .map(|line| line[column_i])
-                .filter(|x| x.is_ascii_alphanumeric())
-                .fold(0, |acc, d| acc * 10 + (d - b'0') as u64);
-            column_i += 1;
-
-            if number != 0 {
-                numbers.push(number);
-            } else {
-                break;
-            }
-        }
+        let numbers = columns.by_ref().take_while(|&number| number != 0);
 
         result += match operator {
-            b'+' => numbers.drain(..).sum::<u64>(),
-            b'*' => numbers.drain(..).product::<u64>(),
+            b'+' => numbers.sum::<u64>(),
+            b'*' => numbers.product::<u64>(),
             _ => unreachable!(),
         };
     }
@@ -84,4 +71,12 @@ mod tests {
         let result = part_two(&input);
         assert_eq!(result, Some(3263827));
     }
+
+    #[test]
+    fn test_part_two_trimmed_lines() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let input: String = input.lines().map(|line| line.trim_end().to_owned() + "\n").collect();
+        let result = part_two(&input);
+        assert_eq!(result, Some(3263827));
+    }
 }
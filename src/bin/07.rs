@@ -2,9 +2,12 @@ advent_of_code::solution!(7);
 
 use advent_of_code_macros::memoize;
 
+use advent_of_code::majcn::grid::*;
 use advent_of_code::maneatingape::grid::*;
 use advent_of_code::maneatingape::point::*;
 
+const SPLIT: [Point; 2] = [LEFT, RIGHT];
+
 fn parse_data(input: &str) -> (Grid<u8>, Point) {
     let grid = Grid::parse(input);
     let start = grid.find(b'S').unwrap();
@@ -17,16 +20,18 @@ pub fn part_one(input: &str) -> Option<u32> {
     grid[start] = b'|';
 
     let mut result = 0;
-    for y in 0..grid.height - 1 {
+    for y in 0..grid.height {
         for x in 0..grid.width {
             let loc = Point::new(x, y);
-            if grid[loc] == b'|' {
-                if grid[loc + DOWN] == b'^' {
+            let below = loc + DOWN;
+            if grid[loc] == b'|' && grid.contains(below) {
+                if grid[below] == b'^' {
                     result += 1;
-                    grid[loc + DOWN + LEFT] = b'|';
-                    grid[loc + DOWN + RIGHT] = b'|';
+                    for split in grid.neighbors(below, &SPLIT, Topology::Bounded) {
+                        grid[split] = b'|';
+                    }
                 } else {
-                    grid[loc + DOWN] = b'|';
+                    grid[below] = b'|';
                 }
             }
         }
@@ -41,17 +46,19 @@ fn solve_cache_key(_: &Grid<u8>, beam_location: Point) -> Point {
 
 #[memoize(key_function = "solve_cache_key -> Point")]
 fn part_two_recursion(grid: &Grid<u8>, beam_location: Point) -> u64 {
-    if beam_location.y == grid.height - 1 {
+    let below = beam_location + DOWN;
+    if !grid.contains(below) {
         return 1;
     }
 
     let mut result = 0;
 
-    if grid[beam_location + DOWN] == b'^' {
-        result += part_two_recursion(grid, beam_location + DOWN + LEFT);
-        result += part_two_recursion(grid, beam_location + DOWN + RIGHT);
+    if grid[below] == b'^' {
+        for split in grid.neighbors(below, &SPLIT, Topology::Bounded) {
+            result += part_two_recursion(grid, split);
+        }
     } else {
-        result += part_two_recursion(grid, beam_location + DOWN);
+        result += part_two_recursion(grid, below);
     }
 
     result
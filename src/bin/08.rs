@@ -1,74 +1,46 @@
 advent_of_code::solution!(8);
 
-use advent_of_code::maneatingape::heap::*;
+use advent_of_code::majcn::input::*;
+use advent_of_code::majcn::kdtree::*;
+use advent_of_code::majcn::point3::*;
+use advent_of_code::majcn::union_find::*;
 use advent_of_code::maneatingape::iter::*;
-use advent_of_code::maneatingape::parse::*;
-
-struct Location {
-    x: u64,
-    y: u64,
-    z: u64,
-}
 
 enum Day8Result {
     Part1(Vec<Vec<usize>>),
     Part2(usize, usize),
 }
 
-fn parse_data(input: &str) -> (Vec<Location>, usize) {
+fn parse_data(input: &str) -> (Vec<Point3>, usize) {
     const DEFAULT_LIMIT: usize = 1000;
 
-    let default_right = format!("{DEFAULT_LIMIT}",);
-    let (left, right) = input.split_once("\n\n").unwrap_or((input, &default_right));
+    let input = Input::new(input);
 
     (
-        left.iter_unsigned().chunk::<3>().map(|[x, y, z]| Location { x, y, z }).collect(),
-        right.unsigned(),
+        input
+            .section(0)
+            .unwrap()
+            .iter_signed()
+            .chunk::<3>()
+            .map(|[x, y, z]| Point3::new(x, y, z))
+            .collect(),
+        input.section(1).map_or(DEFAULT_LIMIT, Section::unsigned),
     )
 }
 
-fn part_x(data: &[Location], limit: usize) -> Day8Result {
-    let mut groups = MinHeap::with_capacity(data.len() * (data.len() - 1) / 2);
-    for i in 0..data.len() {
-        for j in i + 1..data.len() {
-            let dx = u64::abs_diff(data[i].x, data[j].x);
-            let dy = u64::abs_diff(data[i].y, data[j].y);
-            let dz = u64::abs_diff(data[i].z, data[j].z);
-            groups.push(dx * dx + dy * dy + dz * dz, (i, j));
-        }
-    }
+fn part_x(data: &[Point3], limit: usize) -> Day8Result {
+    let tree = KdTree::new(data);
 
-    let generate_new_group = |l1, l2| {
-        let mut new_group = Vec::with_capacity(data.len());
-        new_group.push(l1);
-        new_group.push(l2);
-        new_group
-    };
-
-    let mut final_groups: Vec<Vec<usize>> = Vec::with_capacity(data.len());
-    for _ in 0..limit {
-        let (_, (loc1, loc2)) = groups.pop().unwrap();
-        let loc1_group = final_groups.iter().position(|g| g.contains(&loc1));
-        let loc2_group = final_groups.iter().position(|g| g.contains(&loc2));
-
-        match (loc1_group, loc2_group) {
-            (Some(i), None) => final_groups[i].push(loc2),
-            (None, Some(j)) => final_groups[j].push(loc1),
-            (None, None) => final_groups.push(generate_new_group(loc1, loc2)),
-            (Some(i), Some(j)) if i != j => {
-                let (min_i, max_i) = if i < j { (i, j) } else { (j, i) };
-                let values = final_groups.swap_remove(max_i);
-                final_groups[min_i].extend(values);
-            }
-            _ => { /* do nothing */ }
-        }
+    let mut union_find = UnionFind::new(data.len());
+    for (_, loc1, loc2) in tree.closest_pairs().take(limit) {
+        union_find.union(loc1, loc2);
 
-        if final_groups.len() == 1 && final_groups[0].len() == data.len() {
+        if union_find.components() == 1 {
             return Day8Result::Part2(loc1, loc2);
         }
     }
 
-    Day8Result::Part1(final_groups)
+    Day8Result::Part1(union_find.groups())
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
@@ -85,7 +57,7 @@ pub fn part_two(input: &str) -> Option<u64> {
     let (data, _) = parse_data(input);
 
     let Day8Result::Part2(loc1, loc2) = part_x(&data, usize::MAX) else { unreachable!() };
-    let result = data[loc1].x * data[loc2].x;
+    let result = data[loc1].x as u64 * data[loc2].x as u64;
 
     Some(result)
 }
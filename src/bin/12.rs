@@ -1,7 +1,7 @@
 advent_of_code::solution!(12);
 
+use advent_of_code::majcn::input::*;
 use advent_of_code::maneatingape::iter::*;
-use advent_of_code::maneatingape::parse::*;
 
 struct Shape {
     size: usize,
@@ -14,19 +14,18 @@ struct Instruction {
 }
 
 fn parse_data(input: &str) -> (Vec<Shape>, Vec<Instruction>) {
-    let mut input_split = input.split("\n\n").collect::<Vec<_>>();
-    let instructions_str = input_split.pop().unwrap();
-    let shapes_str = input_split;
+    let input = Input::new(input);
+    let (instructions_section, shapes_sections) = input.split_last().unwrap();
 
-    let instructions = instructions_str
+    let instructions = instructions_section
         .iter_unsigned()
         .chunk::<8>()
         .map(|[w, h, pieces @ ..]| Instruction { width: w, height: h, pieces })
         .collect();
 
-    let shapes = shapes_str
+    let shapes = shapes_sections
         .iter()
-        .map(|s| Shape { size: s.bytes().filter(|&c| c == b'#').count() })
+        .map(|s| Shape { size: s.as_str().bytes().filter(|&c| c == b'#').count() })
         .collect();
 
     (shapes, instructions)
@@ -3,3 +3,7 @@ pub mod template;
 // Use this file to add helper functions and additional modules.
 pub mod majcn;
 pub mod maneatingape;
+
+// Lets macros that expand to `advent_of_code::...` paths be used in unit tests.
+#[cfg(test)]
+extern crate self as advent_of_code;
@@ -0,0 +1,888 @@
+//! Arbitrary-precision integers for answers that overflow even `u128`.
+//!
+//! [`BigUint`] stores its magnitude as a growable vector of 64 bit limbs, least significant limb
+//! first, without leading zero limbs. [`BigInt`] adds a sign on top. Unlike the fixed width
+//! [`UX64`] neither type ever overflows, but neither is `Copy`, so the arithmetic operators are
+//! implemented for references as well as owned values to avoid needless clones.
+//!
+//! Both types work with [`ParseOps`] and [`FromStr`], and implement [`Display`] so that parts
+//! can return them directly. Division truncates towards zero like the primitive types.
+//!
+//! ```
+//!   # use advent_of_code::majcn::bigint::*;
+//!   # use advent_of_code::maneatingape::parse::*;
+//!
+//!   let input = "123456789012345678901 98765432109876543210";
+//!   let product: BigUint = input.iter_unsigned::<BigUint>().product();
+//!   assert_eq!(product.to_string(), "12193263113702179522473403443222511812210");
+//! ```
+//!
+//! [`UX64`]: crate::majcn::bignumbers::UX64
+//! [`ParseOps`]: crate::maneatingape::parse::ParseOps
+use crate::maneatingape::parse::*;
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::iter::{Product, Sum};
+use std::ops::*;
+use std::str::FromStr;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    limbs: Vec<u64>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    magnitude: BigUint,
+}
+
+/// An error which can be returned when parsing a [`BigUint`] or [`BigInt`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseBigIntError {
+    Empty,
+    InvalidDigit,
+}
+
+impl Error for ParseBigIntError {}
+
+impl Display for ParseBigIntError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseBigIntError::Empty => f.write_str("cannot parse integer from empty string"),
+            ParseBigIntError::InvalidDigit => f.write_str("invalid digit found in string"),
+        }
+    }
+}
+
+impl BigUint {
+    pub const ZERO: BigUint = BigUint { limbs: Vec::new() };
+
+    /// Builds a value from 64 bit limbs, least significant first.
+    pub fn from_limbs(mut limbs: Vec<u64>) -> Self {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        BigUint { limbs }
+    }
+
+    #[inline]
+    pub fn limbs(&self) -> &[u64] {
+        &self.limbs
+    }
+
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// Number of bits needed to represent the value, zero for zero.
+    pub fn bits(&self) -> usize {
+        self.limbs.last().map_or(0, |top| 64 * self.limbs.len() - top.leading_zeros() as usize)
+    }
+
+    #[inline]
+    pub fn bit(&self, index: usize) -> bool {
+        self.limbs.get(index / 64).is_some_and(|limb| limb & (1 << (index % 64)) != 0)
+    }
+
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs[..] {
+            [] => Some(0),
+            [a] => Some(a),
+            _ => None,
+        }
+    }
+
+    pub fn to_u128(&self) -> Option<u128> {
+        match self.limbs[..] {
+            [] => Some(0),
+            [a] => Some(a as u128),
+            [a, b] => Some((b as u128) << 64 | a as u128),
+            _ => None,
+        }
+    }
+
+    /// Subtraction that returns `None` instead of panicking when `rhs` is larger.
+    pub fn checked_sub(&self, rhs: &BigUint) -> Option<BigUint> {
+        if *self < *rhs {
+            return None;
+        }
+
+        let mut limbs = self.limbs.clone();
+        let mut borrow = false;
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let other = rhs.limbs.get(i).copied().unwrap_or(0);
+            if other == 0 && !borrow && i >= rhs.limbs.len() {
+                break;
+            }
+            let (difference, b1) = limb.overflowing_sub(other);
+            let (difference, b2) = difference.overflowing_sub(borrow as u64);
+            *limb = difference;
+            borrow = b1 || b2;
+        }
+
+        Some(BigUint::from_limbs(limbs))
+    }
+
+    /// Multiplies by `mul` then adds `add` in place.
+    pub fn mul_add_u64(&mut self, mul: u64, add: u64) {
+        if mul == 0 {
+            *self = BigUint::from(add);
+            return;
+        }
+
+        let mut carry = add as u128;
+        for limb in &mut self.limbs {
+            let current = *limb as u128 * mul as u128 + carry;
+            *limb = current as u64;
+            carry = current >> 64;
+        }
+        if carry > 0 {
+            self.limbs.push(carry as u64);
+        }
+    }
+
+    /// Divides in place by a single limb, returning the remainder.
+    pub fn div_rem_u64(&mut self, rhs: u64) -> u64 {
+        assert!(rhs != 0, "attempt to divide by zero");
+        let mut remainder = 0;
+        for limb in self.limbs.iter_mut().rev() {
+            let current = (remainder as u128) << 64 | *limb as u128;
+            *limb = (current / rhs as u128) as u64;
+            remainder = (current % rhs as u128) as u64;
+        }
+        if self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+        remainder
+    }
+
+    /// Long division using [Knuth's algorithm D](https://skanthak.hier-im-netz.de/division.html),
+    /// returning the quotient and remainder.
+    pub fn div_rem(&self, rhs: &BigUint) -> (BigUint, BigUint) {
+        assert!(!rhs.is_zero(), "attempt to divide by zero");
+
+        if *self < *rhs {
+            return (BigUint::ZERO, self.clone());
+        }
+        if let [divisor] = rhs.limbs[..] {
+            let mut quotient = self.clone();
+            let remainder = quotient.div_rem_u64(divisor);
+            return (quotient, BigUint::from(remainder));
+        }
+
+        // Normalize so that the top bit of the divisor is set, which keeps each estimated
+        // quotient limb at most two above the real one.
+        let shift = rhs.limbs.last().unwrap().leading_zeros();
+        let mut v = shl_limbs(&rhs.limbs, shift);
+        v.pop();
+        let mut u = shl_limbs(&self.limbs, shift);
+
+        let n = v.len();
+        let m = u.len() - n;
+        let (top, second) = (v[n - 1] as u128, v[n - 2] as u128);
+        let mut quotient = vec![0; m];
+
+        for j in (0..m).rev() {
+            let numerator = (u[j + n] as u128) << 64 | u[j + n - 1] as u128;
+            let mut estimate = numerator / top;
+            let mut remainder = numerator % top;
+            while estimate >> 64 != 0
+                || estimate * second > (remainder << 64 | u[j + n - 2] as u128)
+            {
+                estimate -= 1;
+                remainder += top;
+                if remainder >> 64 != 0 {
+                    break;
+                }
+            }
+
+            let mut carry = 0;
+            let mut borrow = false;
+            for i in 0..=n {
+                let product = estimate * v.get(i).copied().unwrap_or(0) as u128 + carry;
+                carry = product >> 64;
+                let (difference, b1) = u[i + j].overflowing_sub(product as u64);
+                let (difference, b2) = difference.overflowing_sub(borrow as u64);
+                u[i + j] = difference;
+                borrow = b1 || b2;
+            }
+
+            // The estimate was one too large, so add the divisor back once.
+            if borrow {
+                estimate -= 1;
+                let mut carry = 0;
+                for i in 0..=n {
+                    let sum = u[i + j] as u128 + v.get(i).copied().unwrap_or(0) as u128 + carry;
+                    u[i + j] = sum as u64;
+                    carry = sum >> 64;
+                }
+            }
+
+            quotient[j] = estimate as u64;
+        }
+
+        u.truncate(n);
+        let remainder = shr_limbs(&u, shift);
+        (BigUint::from_limbs(quotient), BigUint::from_limbs(remainder))
+    }
+
+    /// Exponentiation by squaring.
+    pub fn pow(&self, mut exponent: u32) -> BigUint {
+        let mut base = self.clone();
+        let mut result = BigUint::from(1u8);
+
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &base;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = &base * &base;
+            }
+        }
+
+        result
+    }
+
+    /// Modular exponentiation, calculating `selfᵉ mod m`.
+    pub fn mod_pow(&self, exponent: &BigUint, modulus: &BigUint) -> BigUint {
+        let mut base = self % modulus;
+        let mut result = BigUint::from(1u8) % modulus;
+
+        for i in 0..exponent.bits() {
+            if exponent.bit(i) {
+                result = &(&result * &base) % modulus;
+            }
+            base = &(&base * &base) % modulus;
+        }
+
+        result
+    }
+}
+
+/// Shifts left by less than 64 bits, always adding one extra limb for the overflow.
+fn shl_limbs(limbs: &[u64], shift: u32) -> Vec<u64> {
+    let mut result = Vec::with_capacity(limbs.len() + 1);
+    let mut carry = 0;
+    for &limb in limbs {
+        result.push(limb << shift | carry);
+        carry = if shift == 0 { 0 } else { limb >> (64 - shift) };
+    }
+    result.push(carry);
+    result
+}
+
+/// Shifts right by less than 64 bits.
+fn shr_limbs(limbs: &[u64], shift: u32) -> Vec<u64> {
+    if shift == 0 {
+        return limbs.to_vec();
+    }
+    let mut result = vec![0; limbs.len()];
+    for (i, &limb) in limbs.iter().enumerate() {
+        let next = limbs.get(i + 1).copied().unwrap_or(0);
+        result[i] = limb >> shift | next << (64 - shift);
+    }
+    result
+}
+
+impl BigInt {
+    pub const ZERO: BigInt = BigInt { negative: false, magnitude: BigUint::ZERO };
+
+    /// Combines a sign and magnitude, where zero is never negative.
+    pub fn from_parts(negative: bool, magnitude: BigUint) -> Self {
+        BigInt { negative: negative && !magnitude.is_zero(), magnitude }
+    }
+
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_zero()
+    }
+
+    #[inline]
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    #[inline]
+    pub fn magnitude(&self) -> &BigUint {
+        &self.magnitude
+    }
+
+    #[inline]
+    pub fn into_magnitude(self) -> BigUint {
+        self.magnitude
+    }
+
+    pub fn to_i64(&self) -> Option<i64> {
+        let magnitude = self.magnitude.to_u64()?;
+        if self.negative {
+            0i64.checked_sub_unsigned(magnitude)
+        } else {
+            i64::try_from(magnitude).ok()
+        }
+    }
+
+    pub fn to_i128(&self) -> Option<i128> {
+        let magnitude = self.magnitude.to_u128()?;
+        if self.negative {
+            0i128.checked_sub_unsigned(magnitude)
+        } else {
+            i128::try_from(magnitude).ok()
+        }
+    }
+
+    /// Truncating division, returning the quotient and remainder.
+    pub fn div_rem(&self, rhs: &BigInt) -> (BigInt, BigInt) {
+        let (quotient, remainder) = self.magnitude.div_rem(&rhs.magnitude);
+        (
+            BigInt::from_parts(self.negative != rhs.negative, quotient),
+            BigInt::from_parts(self.negative, remainder),
+        )
+    }
+
+    /// Least non-negative remainder of dividing by `modulus`.
+    pub fn rem_euclid(&self, modulus: &BigUint) -> BigUint {
+        let remainder = &self.magnitude % modulus;
+        if self.negative && !remainder.is_zero() { modulus - &remainder } else { remainder }
+    }
+
+    pub fn pow(&self, exponent: u32) -> BigInt {
+        BigInt::from_parts(self.negative && exponent % 2 == 1, self.magnitude.pow(exponent))
+    }
+
+    /// Modular exponentiation, calculating `selfᵉ mod m` in the range `0..m`.
+    pub fn mod_pow(&self, exponent: &BigUint, modulus: &BigUint) -> BigUint {
+        self.rem_euclid(modulus).mod_pow(exponent, modulus)
+    }
+}
+
+macro_rules! from_unsigned {
+    ($($t:ty)*) => ($(
+        impl From<$t> for BigUint {
+            #[inline]
+            fn from(value: $t) -> Self {
+                BigUint::from(value as u128)
+            }
+        }
+
+        impl From<$t> for BigInt {
+            #[inline]
+            fn from(value: $t) -> Self {
+                BigInt::from(BigUint::from(value))
+            }
+        }
+    )*)
+}
+
+macro_rules! from_signed {
+    ($($t:ty)*) => ($(
+        impl From<$t> for BigInt {
+            #[inline]
+            fn from(value: $t) -> Self {
+                BigInt::from_parts(value < 0, BigUint::from(value.unsigned_abs()))
+            }
+        }
+    )*)
+}
+
+from_unsigned!(u8 u16 u32 u64 usize);
+from_signed!(i8 i16 i32 i64 i128 isize);
+
+impl From<u128> for BigUint {
+    fn from(value: u128) -> Self {
+        BigUint::from_limbs(vec![value as u64, (value >> 64) as u64])
+    }
+}
+
+impl From<u128> for BigInt {
+    fn from(value: u128) -> Self {
+        BigInt::from(BigUint::from(value))
+    }
+}
+
+impl From<BigUint> for BigInt {
+    #[inline]
+    fn from(magnitude: BigUint) -> Self {
+        BigInt { negative: false, magnitude }
+    }
+}
+
+impl PartialOrd for BigUint {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigInt {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, false) => self.magnitude.cmp(&other.magnitude),
+            (true, true) => other.magnitude.cmp(&self.magnitude),
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+        }
+    }
+}
+
+impl Add<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, rhs: &BigUint) -> BigUint {
+        let (long, short) =
+            if self.limbs.len() >= rhs.limbs.len() { (self, rhs) } else { (rhs, self) };
+        let mut limbs = long.limbs.clone();
+        let mut carry = false;
+
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let other = short.limbs.get(i).copied().unwrap_or(0);
+            if other == 0 && !carry && i >= short.limbs.len() {
+                break;
+            }
+            let (sum, c1) = limb.overflowing_add(other);
+            let (sum, c2) = sum.overflowing_add(carry as u64);
+            *limb = sum;
+            carry = c1 || c2;
+        }
+        if carry {
+            limbs.push(1);
+        }
+
+        BigUint { limbs }
+    }
+}
+
+impl Sub<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    /// Panics if `rhs` is larger, like the primitive unsigned types in debug builds.
+    fn sub(self, rhs: &BigUint) -> BigUint {
+        self.checked_sub(rhs).expect("attempt to subtract with overflow")
+    }
+}
+
+impl Mul<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, rhs: &BigUint) -> BigUint {
+        if self.is_zero() || rhs.is_zero() {
+            return BigUint::ZERO;
+        }
+
+        let mut limbs = vec![0; self.limbs.len() + rhs.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0;
+            for (j, &b) in rhs.limbs.iter().enumerate() {
+                let current = a as u128 * b as u128 + limbs[i + j] as u128 + carry;
+                limbs[i + j] = current as u64;
+                carry = current >> 64;
+            }
+            limbs[i + rhs.limbs.len()] = carry as u64;
+        }
+
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl Div<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    #[inline]
+    fn div(self, rhs: &BigUint) -> BigUint {
+        self.div_rem(rhs).0
+    }
+}
+
+impl Rem<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    #[inline]
+    fn rem(self, rhs: &BigUint) -> BigUint {
+        self.div_rem(rhs).1
+    }
+}
+
+impl Add<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, rhs: &BigInt) -> BigInt {
+        if self.negative == rhs.negative {
+            return BigInt::from_parts(self.negative, &self.magnitude + &rhs.magnitude);
+        }
+        match self.magnitude.cmp(&rhs.magnitude) {
+            Ordering::Less => BigInt::from_parts(rhs.negative, &rhs.magnitude - &self.magnitude),
+            _ => BigInt::from_parts(self.negative, &self.magnitude - &rhs.magnitude),
+        }
+    }
+}
+
+impl Sub<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    #[inline]
+    fn sub(self, rhs: &BigInt) -> BigInt {
+        self + &-rhs
+    }
+}
+
+impl Mul<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    #[inline]
+    fn mul(self, rhs: &BigInt) -> BigInt {
+        BigInt::from_parts(self.negative != rhs.negative, &self.magnitude * &rhs.magnitude)
+    }
+}
+
+impl Div<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    #[inline]
+    fn div(self, rhs: &BigInt) -> BigInt {
+        self.div_rem(rhs).0
+    }
+}
+
+impl Rem<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    #[inline]
+    fn rem(self, rhs: &BigInt) -> BigInt {
+        self.div_rem(rhs).1
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    #[inline]
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.magnitude.clone())
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    #[inline]
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.magnitude)
+    }
+}
+
+/// Implements the owned and assigning variants of an operator on top of the one for references.
+macro_rules! forward {
+    ($t:ty, $op:ident, $method:ident, $op_assign:ident, $method_assign:ident) => {
+        impl $op<$t> for $t {
+            type Output = $t;
+
+            #[inline]
+            fn $method(self, rhs: $t) -> $t {
+                (&self).$method(&rhs)
+            }
+        }
+
+        impl $op<&$t> for $t {
+            type Output = $t;
+
+            #[inline]
+            fn $method(self, rhs: &$t) -> $t {
+                (&self).$method(rhs)
+            }
+        }
+
+        impl $op<$t> for &$t {
+            type Output = $t;
+
+            #[inline]
+            fn $method(self, rhs: $t) -> $t {
+                self.$method(&rhs)
+            }
+        }
+
+        impl $op_assign<$t> for $t {
+            #[inline]
+            fn $method_assign(&mut self, rhs: $t) {
+                *self = (&*self).$method(&rhs);
+            }
+        }
+
+        impl $op_assign<&$t> for $t {
+            #[inline]
+            fn $method_assign(&mut self, rhs: &$t) {
+                *self = (&*self).$method(rhs);
+            }
+        }
+    };
+}
+
+forward!(BigUint, Add, add, AddAssign, add_assign);
+forward!(BigUint, Sub, sub, SubAssign, sub_assign);
+forward!(BigUint, Mul, mul, MulAssign, mul_assign);
+forward!(BigUint, Div, div, DivAssign, div_assign);
+forward!(BigUint, Rem, rem, RemAssign, rem_assign);
+forward!(BigInt, Add, add, AddAssign, add_assign);
+forward!(BigInt, Sub, sub, SubAssign, sub_assign);
+forward!(BigInt, Mul, mul, MulAssign, mul_assign);
+forward!(BigInt, Div, div, DivAssign, div_assign);
+forward!(BigInt, Rem, rem, RemAssign, rem_assign);
+
+macro_rules! fold {
+    ($t:ty) => {
+        impl Sum for $t {
+            fn sum<I: Iterator<Item = $t>>(iter: I) -> $t {
+                iter.fold(<$t>::ZERO, |acc, n| acc + n)
+            }
+        }
+
+        impl<'a> Sum<&'a $t> for $t {
+            fn sum<I: Iterator<Item = &'a $t>>(iter: I) -> $t {
+                iter.fold(<$t>::ZERO, |acc, n| acc + n)
+            }
+        }
+
+        impl Product for $t {
+            fn product<I: Iterator<Item = $t>>(iter: I) -> $t {
+                iter.fold(<$t>::from(1u8), |acc, n| acc * n)
+            }
+        }
+
+        impl<'a> Product<&'a $t> for $t {
+            fn product<I: Iterator<Item = &'a $t>>(iter: I) -> $t {
+                iter.fold(<$t>::from(1u8), |acc, n| acc * n)
+            }
+        }
+    };
+}
+
+fold!(BigUint);
+fold!(BigInt);
+
+impl FromDigits for BigUint {
+    #[inline]
+    fn from_digit(digit: u8) -> Self {
+        BigUint::from(digit)
+    }
+
+    #[inline]
+    fn push_digit(mut self, radix: u8, digit: u8) -> Self {
+        self.mul_add_u64(radix as u64, digit as u64);
+        self
+    }
+}
+
+impl UnsignedDigits for BigUint {}
+
+impl FromDigits for BigInt {
+    #[inline]
+    fn from_digit(digit: u8) -> Self {
+        BigInt::from(digit)
+    }
+
+    /// Digits extend the magnitude, so parsing negates only once all digits are read.
+    #[inline]
+    fn push_digit(self, radix: u8, digit: u8) -> Self {
+        BigInt::from_parts(self.negative, self.magnitude.push_digit(radix, digit))
+    }
+}
+
+impl SignedDigits for BigInt {}
+
+impl FromStr for BigUint {
+    type Err = ParseBigIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.strip_prefix('+').unwrap_or(s);
+        if digits.is_empty() {
+            return Err(ParseBigIntError::Empty);
+        }
+
+        digits.bytes().try_fold(BigUint::ZERO, |n, b| {
+            let digit = b.to_decimal();
+            if digit < 10 {
+                Ok(n.push_digit(10, digit))
+            } else {
+                Err(ParseBigIntError::InvalidDigit)
+            }
+        })
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = ParseBigIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix('-') {
+            Some(rest) if !rest.starts_with('+') => Ok(-BigInt::from(rest.parse::<BigUint>()?)),
+            Some(_) => Err(ParseBigIntError::InvalidDigit),
+            None => Ok(BigInt::from(s.parse::<BigUint>()?)),
+        }
+    }
+}
+
+impl Display for BigUint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+
+        let mut chunks = vec![];
+        let mut value = self.clone();
+        loop {
+            chunks.push(value.div_rem_u64(CHUNK));
+            if value.is_zero() {
+                break;
+            }
+        }
+
+        let mut digits = chunks.pop().unwrap().to_string();
+        chunks.iter().rev().for_each(|chunk| digits += &format!("{chunk:019}"));
+        f.pad_integral(true, "", &digits)
+    }
+}
+
+impl Display for BigInt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let digits = self.magnitude.to_string();
+        f.pad_integral(!self.negative, "", &digits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::majcn::testing::Rng;
+
+    fn value(rng: &mut Rng) -> u128 {
+        rng.next_u128() >> rng.below(128)
+    }
+
+    fn signed(rng: &mut Rng) -> i128 {
+        let value = (value(rng) >> 1) as i128;
+        if rng.below(2) == 0 { value } else { -value }
+    }
+
+    fn limbs(rng: &mut Rng, max: u64) -> BigUint {
+        let len = rng.below(max) + 1;
+        BigUint::from_limbs((0..len).map(|_| rng.next() >> rng.below(64)).collect())
+    }
+
+    #[test]
+    fn test_unsigned_against_u128() {
+        let mut rng = Rng::new();
+
+        for _ in 0..10_000 {
+            let (a, b) = (value(&mut rng), value(&mut rng));
+            let (x, y) = (BigUint::from(a), BigUint::from(b));
+
+            assert_eq!(x.cmp(&y), a.cmp(&b));
+            assert_eq!((&x + &y).to_u128(), a.checked_add(b));
+            assert_eq!(x.checked_sub(&y).and_then(|n| n.to_u128()), a.checked_sub(b));
+            assert_eq!((&x * &y).to_u128(), a.checked_mul(b));
+            if let (Some(quotient), Some(remainder)) = (a.checked_div(b), a.checked_rem(b)) {
+                let (x, y) = x.div_rem(&y);
+                assert_eq!((x.to_u128(), y.to_u128()), (Some(quotient), Some(remainder)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_signed_against_i128() {
+        let mut rng = Rng::new();
+
+        for _ in 0..10_000 {
+            let (a, b) = (signed(&mut rng), signed(&mut rng));
+            let (x, y) = (BigInt::from(a), BigInt::from(b));
+
+            assert_eq!(x.cmp(&y), a.cmp(&b));
+            assert_eq!((&x + &y).to_i128(), a.checked_add(b));
+            assert_eq!((&x - &y).to_i128(), a.checked_sub(b));
+            assert_eq!((&x * &y).to_i128(), a.checked_mul(b));
+            if let (Some(quotient), Some(remainder)) = (a.checked_div(b), a.checked_rem(b)) {
+                let (x, y) = x.div_rem(&y);
+                assert_eq!((x.to_i128(), y.to_i128()), (Some(quotient), Some(remainder)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_multi_limb_division() {
+        let mut rng = Rng::new();
+
+        for _ in 0..2_000 {
+            let (a, b) = (limbs(&mut rng, 6), limbs(&mut rng, 4));
+            if b.is_zero() {
+                continue;
+            }
+            let r = &limbs(&mut rng, 4) % &b;
+            let n = &(&a * &b) + &r;
+
+            assert_eq!(n.div_rem(&b), (a.clone(), r.clone()));
+            let (quotient, remainder) = a.div_rem(&b);
+            assert!(remainder < b);
+            assert_eq!(&(&quotient * &b) + &remainder, a);
+        }
+    }
+
+    #[test]
+    fn test_division_add_back() {
+        // The first estimated quotient limb is one too large and is only corrected after
+        // subtracting, so the divisor has to be added back.
+        let u = BigUint::from_limbs(vec![0, 0, 0, 1]);
+        let v = BigUint::from_limbs(vec![1, 0, 1]);
+        let quotient = BigUint::from(u64::MAX);
+        let remainder = BigUint::from_limbs(vec![1, u64::MAX]);
+        assert_eq!(u.div_rem(&v), (quotient, remainder));
+
+        let u = BigUint::from_limbs(vec![0, 0, 0, 1]);
+        let v = BigUint::from_limbs(vec![3, 0, 1 << 63]);
+        let (quotient, remainder) = u.div_rem(&v);
+        assert!(remainder < v);
+        assert_eq!(&(&quotient * &v) + &remainder, u);
+    }
+
+    #[test]
+    fn test_display_parse() {
+        let mut rng = Rng::new();
+
+        for _ in 0..1_000 {
+            let a = value(&mut rng);
+            assert_eq!(BigUint::from(a).to_string(), a.to_string());
+            assert_eq!(a.to_string().parse(), Ok(BigUint::from(a)));
+
+            let b = -((a >> 1) as i128);
+            assert_eq!(BigInt::from(b).to_string(), b.to_string());
+            assert_eq!(b.to_string().parse(), Ok(BigInt::from(b)));
+        }
+
+        let big = "123456789012345678901234567890123456789012345678901234567890";
+        assert_eq!(big.parse::<BigUint>().unwrap().to_string(), big);
+        assert_eq!(format!("-{big}").parse::<BigInt>().unwrap().to_string(), format!("-{big}"));
+        assert_eq!("".parse::<BigUint>(), Err(ParseBigIntError::Empty));
+        assert_eq!("-+1".parse::<BigInt>(), Err(ParseBigIntError::InvalidDigit));
+        assert_eq!("-0".parse::<BigInt>(), Ok(BigInt::ZERO));
+
+        let input = format!("x=12, y=-34 z={big}");
+        let unsigned: Vec<BigUint> = input.iter_unsigned().collect();
+        assert_eq!(unsigned, [12u8.into(), 34u8.into(), big.parse().unwrap()]);
+        let signed: Vec<BigInt> = input.iter_signed().collect();
+        assert_eq!(signed, [12.into(), (-34).into(), big.parse().unwrap()]);
+    }
+}
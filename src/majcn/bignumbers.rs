@@ -1,20 +1,333 @@
-#[derive(Clone, Copy)]
+//! Fixed width unsigned integer of `N` 64 bit words, stored least significant word first.
+//!
+//! Arithmetic wraps around on overflow like the `wrapping_*` methods of the primitive types.
+//! Use [`overflowing_add`] and [`overflowing_sub`] to get the carry or borrow as well.
+//!
+//! `UX64` implements [`Integer`] and [`Unsigned`], so the generic helpers such as
+//! [`biterator`], [`gcd`] and [`mod_pow`] as well as [`ParseOps`] work on wide values. Note that
+//! [`mod_pow`] multiplies two values below the modulus without widening, so the modulus must
+//! fit in half the bits.
+//!
+//! [`overflowing_add`]: UX64::overflowing_add
+//! [`overflowing_sub`]: UX64::overflowing_sub
+//! [`biterator`]: crate::maneatingape::bitset::BitOps::biterator
+//! [`gcd`]: crate::maneatingape::math::IntegerMathOps::gcd
+//! [`mod_pow`]: crate::maneatingape::math::IntegerMathOps::mod_pow
+//! [`ParseOps`]: crate::maneatingape::parse::ParseOps
+use crate::maneatingape::integer::*;
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::{Binary, Display, Formatter, LowerHex, UpperHex};
+use std::ops::*;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct UX64<const N: usize>([u64; N]);
 
-impl<const N: usize> UX64<N> {
-    pub fn count_ones(&self) -> u32 {
-        self.0.iter().map(|x| x.count_ones()).sum()
+/// An error which can be returned when parsing a [`UX64`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseUX64Error {
+    Empty,
+    InvalidDigit,
+    Overflow,
+}
+
+impl Error for ParseUX64Error {}
+
+impl Display for ParseUX64Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseUX64Error::Empty => f.write_str("cannot parse integer from empty string"),
+            ParseUX64Error::InvalidDigit => f.write_str("invalid digit found in string"),
+            ParseUX64Error::Overflow => f.write_str("number too large to fit in target type"),
+        }
     }
+}
 
+impl<const N: usize> UX64<N> {
     pub const ZERO: UX64<N> = UX64([0; N]);
     pub const ONE: UX64<N> = {
         let mut result = UX64([0; N]);
         result.0[0] = 1;
         result
     };
+    pub const MAX: UX64<N> = UX64([u64::MAX; N]);
+    pub const BITS: u32 = 64 * N as u32;
+
+    /// Creates a value from its words, least significant first.
+    #[inline]
+    pub const fn from_words(words: [u64; N]) -> Self {
+        UX64(words)
+    }
+
+    #[inline]
+    pub const fn words(&self) -> &[u64; N] {
+        &self.0
+    }
+
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        self.0.iter().all(|&w| w == 0)
+    }
+
+    pub fn count_ones(&self) -> u32 {
+        self.0.iter().map(|x| x.count_ones()).sum()
+    }
+
+    #[inline]
+    pub fn trailing_zeros(&self) -> u32 {
+        match self.0.iter().position(|&w| w != 0) {
+            Some(i) => 64 * i as u32 + self.0[i].trailing_zeros(),
+            None => Self::BITS,
+        }
+    }
+
+    #[inline]
+    pub fn leading_zeros(&self) -> u32 {
+        match self.0.iter().rposition(|&w| w != 0) {
+            Some(i) => 64 * (N - 1 - i) as u32 + self.0[i].leading_zeros(),
+            None => Self::BITS,
+        }
+    }
+
+    #[inline]
+    pub fn bit(&self, index: usize) -> bool {
+        self.0[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    #[inline]
+    pub fn set_bit(&mut self, index: usize, value: bool) {
+        let mask = 1 << (index % 64);
+        if value {
+            self.0[index / 64] |= mask;
+        } else {
+            self.0[index / 64] &= !mask;
+        }
+    }
+
+    pub fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+        let mut result = UX64::ZERO;
+        let mut carry = false;
+        for i in 0..N {
+            let (sum, c1) = self.0[i].overflowing_add(rhs.0[i]);
+            let (sum, c2) = sum.overflowing_add(carry as u64);
+            result.0[i] = sum;
+            carry = c1 || c2;
+        }
+        (result, carry)
+    }
+
+    pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+        let mut result = UX64::ZERO;
+        let mut borrow = false;
+        for i in 0..N {
+            let (diff, b1) = self.0[i].overflowing_sub(rhs.0[i]);
+            let (diff, b2) = diff.overflowing_sub(borrow as u64);
+            result.0[i] = diff;
+            borrow = b1 || b2;
+        }
+        (result, borrow)
+    }
+
+    #[inline]
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        let (result, carry) = self.overflowing_add(rhs);
+        (!carry).then_some(result)
+    }
+
+    #[inline]
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        let (result, borrow) = self.overflowing_sub(rhs);
+        (!borrow).then_some(result)
+    }
+
+    /// Multiplies by a single word, returning the word that overflowed.
+    pub fn overflowing_mul_u64(self, rhs: u64) -> (Self, u64) {
+        let mut result = UX64::ZERO;
+        let mut carry = 0;
+        for i in 0..N {
+            let product = self.0[i] as u128 * rhs as u128 + carry as u128;
+            result.0[i] = product as u64;
+            carry = (product >> 64) as u64;
+        }
+        (result, carry)
+    }
+
+    /// Divides by a single word, returning the quotient and remainder.
+    pub fn div_rem_u64(self, rhs: u64) -> (Self, u64) {
+        assert!(rhs != 0, "attempt to divide by zero");
+        let mut result = UX64::ZERO;
+        let mut remainder = 0;
+        for i in (0..N).rev() {
+            let current = (remainder as u128) << 64 | self.0[i] as u128;
+            result.0[i] = (current / rhs as u128) as u64;
+            remainder = (current % rhs as u128) as u64;
+        }
+        (result, remainder)
+    }
+
+    /// Binary long division, returning the quotient and remainder.
+    pub fn div_rem(self, rhs: Self) -> (Self, Self) {
+        assert!(!rhs.is_zero(), "attempt to divide by zero");
+        if rhs.0[1..].iter().all(|&w| w == 0) {
+            let (quotient, remainder) = self.div_rem_u64(rhs.0[0]);
+            return (quotient, remainder.into());
+        }
+
+        let mut quotient = UX64::ZERO;
+        let mut remainder = UX64::ZERO;
+        for i in (0..(Self::BITS - self.leading_zeros()) as usize).rev() {
+            let carry = remainder.bit(Self::BITS as usize - 1);
+            remainder = remainder << 1usize;
+            remainder.set_bit(0, self.bit(i));
+            if carry || remainder >= rhs {
+                remainder -= rhs;
+                quotient.set_bit(i, true);
+            }
+        }
+        (quotient, remainder)
+    }
+
+    /// Parses digits in any base from 2 to 36 without a sign or prefix.
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseUX64Error> {
+        if s.is_empty() {
+            return Err(ParseUX64Error::Empty);
+        }
+
+        s.chars().try_fold(UX64::ZERO, |acc: Self, c| {
+            let digit = c.to_digit(radix).ok_or(ParseUX64Error::InvalidDigit)?;
+            let (shifted, carry) = acc.overflowing_mul_u64(radix as u64);
+            let (result, overflow) = shifted.overflowing_add((digit as u64).into());
+            if carry != 0 || overflow { Err(ParseUX64Error::Overflow) } else { Ok(result) }
+        })
+    }
+}
+
+impl<const N: usize> From<u8> for UX64<N> {
+    #[inline]
+    fn from(value: u8) -> Self {
+        (value as u64).into()
+    }
+}
+
+impl<const N: usize> From<u32> for UX64<N> {
+    #[inline]
+    fn from(value: u32) -> Self {
+        (value as u64).into()
+    }
+}
+
+impl<const N: usize> From<u64> for UX64<N> {
+    #[inline]
+    fn from(value: u64) -> Self {
+        let mut result = UX64::ZERO;
+        result.0[0] = value;
+        result
+    }
+}
+
+impl<const N: usize> From<u128> for UX64<N> {
+    /// Truncates if `N` is 1.
+    #[inline]
+    fn from(value: u128) -> Self {
+        let mut result = UX64::from(value as u64);
+        if N > 1 {
+            result.0[1] = (value >> 64) as u64;
+        }
+        result
+    }
+}
+
+impl<const N: usize> PartialOrd for UX64<N> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for UX64<N> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl<const N: usize> Add for UX64<N> {
+    type Output = UX64<N>;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        self.overflowing_add(rhs).0
+    }
+}
+
+impl<const N: usize> AddAssign for UX64<N> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const N: usize> Sub for UX64<N> {
+    type Output = UX64<N>;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.overflowing_sub(rhs).0
+    }
+}
+
+impl<const N: usize> SubAssign for UX64<N> {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const N: usize> Mul for UX64<N> {
+    type Output = UX64<N>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut result = UX64::ZERO;
+        for i in 0..N {
+            let mut carry = 0;
+            for j in 0..N - i {
+                let current =
+                    self.0[i] as u128 * rhs.0[j] as u128 + result.0[i + j] as u128 + carry as u128;
+                result.0[i + j] = current as u64;
+                carry = (current >> 64) as u64;
+            }
+        }
+        result
+    }
+}
+
+impl<const N: usize> MulAssign for UX64<N> {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const N: usize> Div for UX64<N> {
+    type Output = UX64<N>;
+
+    #[inline]
+    fn div(self, rhs: Self) -> Self::Output {
+        self.div_rem(rhs).0
+    }
 }
 
-impl<const N: usize> std::ops::Shl<usize> for UX64<N> {
+impl<const N: usize> Rem for UX64<N> {
+    type Output = UX64<N>;
+
+    #[inline]
+    fn rem(self, rhs: Self) -> Self::Output {
+        self.div_rem(rhs).1
+    }
+}
+
+impl<const N: usize> Shl<usize> for UX64<N> {
     type Output = UX64<N>;
 
     fn shl(self, rhs: usize) -> Self::Output {
@@ -33,7 +346,7 @@ impl<const N: usize> std::ops::Shl<usize> for UX64<N> {
     }
 }
 
-impl<const N: usize> std::ops::Shr<usize> for UX64<N> {
+impl<const N: usize> Shr<usize> for UX64<N> {
     type Output = UX64<N>;
 
     fn shr(self, shift: usize) -> Self::Output {
@@ -50,7 +363,25 @@ impl<const N: usize> std::ops::Shr<usize> for UX64<N> {
     }
 }
 
-impl<const N: usize> std::ops::BitAnd for UX64<N> {
+impl<const N: usize> Shl<u32> for UX64<N> {
+    type Output = UX64<N>;
+
+    #[inline]
+    fn shl(self, rhs: u32) -> Self::Output {
+        self << rhs as usize
+    }
+}
+
+impl<const N: usize> Shr<u32> for UX64<N> {
+    type Output = UX64<N>;
+
+    #[inline]
+    fn shr(self, rhs: u32) -> Self::Output {
+        self >> rhs as usize
+    }
+}
+
+impl<const N: usize> BitAnd for UX64<N> {
     type Output = UX64<N>;
 
     fn bitand(self, rhs: Self) -> Self::Output {
@@ -62,7 +393,15 @@ impl<const N: usize> std::ops::BitAnd for UX64<N> {
     }
 }
 
-impl<const N: usize> std::ops::BitOr for UX64<N> {
+impl<const N: usize> BitAndAssign for UX64<N> {
+    fn bitand_assign(&mut self, rhs: Self) {
+        for i in 0..N {
+            self.0[i] &= rhs.0[i];
+        }
+    }
+}
+
+impl<const N: usize> BitOr for UX64<N> {
     type Output = UX64<N>;
 
     fn bitor(self, rhs: Self) -> Self::Output {
@@ -74,7 +413,7 @@ impl<const N: usize> std::ops::BitOr for UX64<N> {
     }
 }
 
-impl<const N: usize> std::ops::BitOrAssign for UX64<N> {
+impl<const N: usize> BitOrAssign for UX64<N> {
     fn bitor_assign(&mut self, rhs: Self) {
         for i in 0..N {
             self.0[i] = self.0[i] | rhs.0[i];
@@ -82,7 +421,27 @@ impl<const N: usize> std::ops::BitOrAssign for UX64<N> {
     }
 }
 
-impl<const N: usize> std::ops::Not for UX64<N> {
+impl<const N: usize> BitXor for UX64<N> {
+    type Output = UX64<N>;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        let mut result = UX64::ZERO;
+        for i in 0..N {
+            result.0[i] = self.0[i] ^ rhs.0[i];
+        }
+        result
+    }
+}
+
+impl<const N: usize> BitXorAssign for UX64<N> {
+    fn bitxor_assign(&mut self, rhs: Self) {
+        for i in 0..N {
+            self.0[i] ^= rhs.0[i];
+        }
+    }
+}
+
+impl<const N: usize> Not for UX64<N> {
     type Output = UX64<N>;
 
     fn not(self) -> Self::Output {
@@ -93,3 +452,173 @@ impl<const N: usize> std::ops::Not for UX64<N> {
         result
     }
 }
+
+impl<const N: usize> Integer<UX64<N>> for UX64<N> {
+    const ZERO: UX64<N> = UX64::ZERO;
+    const ONE: UX64<N> = UX64::ONE;
+    const TEN: UX64<N> = {
+        let mut result = UX64([0; N]);
+        result.0[0] = 10;
+        result
+    };
+
+    #[inline]
+    fn trailing_zeros(self) -> u32 {
+        UX64::trailing_zeros(&self)
+    }
+
+    /// There is no wider type, so the product must fit in `N` words.
+    #[inline]
+    fn mul_mod(self, rhs: UX64<N>, m: UX64<N>) -> UX64<N> {
+        (self * rhs) % m
+    }
+}
+
+impl<const N: usize> Unsigned<UX64<N>> for UX64<N> {}
+
+impl<const N: usize> FromStr for UX64<N> {
+    type Err = ParseUX64Error;
+
+    /// Parses decimal digits, or hexadecimal digits after a `0x` prefix.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix("0x") {
+            Some(hex) => Self::from_str_radix(hex, 16),
+            None => Self::from_str_radix(s, 10),
+        }
+    }
+}
+
+impl<const N: usize> Display for UX64<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+
+        let mut chunks = vec![];
+        let mut value = *self;
+        loop {
+            let (quotient, remainder) = value.div_rem_u64(CHUNK);
+            chunks.push(remainder);
+            value = quotient;
+            if value.is_zero() {
+                break;
+            }
+        }
+
+        let mut digits = chunks.pop().unwrap().to_string();
+        chunks.iter().rev().for_each(|chunk| digits += &format!("{chunk:019}"));
+        f.pad_integral(true, "", &digits)
+    }
+}
+
+macro_rules! radix {
+    ($trait:ident, $prefix:literal, $fmt:literal, $short:literal) => {
+        impl<const N: usize> $trait for UX64<N> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                let top = self.0.iter().rposition(|&w| w != 0).unwrap_or(0);
+                let mut digits = format!($short, self.0[top]);
+                self.0[..top].iter().rev().for_each(|w| digits += &format!($fmt, w));
+                f.pad_integral(true, $prefix, &digits)
+            }
+        }
+    };
+}
+
+radix!(LowerHex, "0x", "{:016x}", "{:x}");
+radix!(UpperHex, "0x", "{:016X}", "{:X}");
+radix!(Binary, "0b", "{:064b}", "{:b}");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::majcn::testing::Rng;
+
+    type U128 = UX64<2>;
+
+    /// Values of every bit length, including all ones and the top bit set.
+    fn value(rng: &mut Rng) -> u128 {
+        let value = rng.next_u128();
+        match rng.below(4) {
+            0 => value,
+            1 => value | 1 << 127,
+            2 => u128::MAX >> rng.below(128),
+            _ => value >> rng.below(128),
+        }
+    }
+
+    fn wide(value: U128) -> u128 {
+        (value.0[1] as u128) << 64 | value.0[0] as u128
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let mut rng = Rng::new();
+
+        for _ in 0..10_000 {
+            let (a, b) = (value(&mut rng), value(&mut rng));
+            let (x, y) = (U128::from(a), U128::from(b));
+
+            assert_eq!(wide(x + y), a.wrapping_add(b));
+            assert_eq!(wide(x - y), a.wrapping_sub(b));
+            assert_eq!(wide(x * y), a.wrapping_mul(b));
+            assert_eq!(x.cmp(&y), a.cmp(&b));
+
+            let (sum, carry) = x.overflowing_add(y);
+            assert_eq!((wide(sum), carry), a.overflowing_add(b));
+            let (difference, borrow) = x.overflowing_sub(y);
+            assert_eq!((wide(difference), borrow), a.overflowing_sub(b));
+
+            if let (Some(quotient), Some(remainder)) = (a.checked_div(b), a.checked_rem(b)) {
+                let (x, y) = x.div_rem(y);
+                assert_eq!((wide(x), wide(y)), (quotient, remainder));
+            }
+        }
+    }
+
+    #[test]
+    fn test_div_rem_top_bit() {
+        let a = u128::MAX - 12345;
+        for b in [1 << 127, u128::MAX, u128::MAX / 3 * 2, 1 << 64 | 1, (1 << 127) + 1] {
+            let (quotient, remainder) = U128::from(a).div_rem(U128::from(b));
+            assert_eq!((wide(quotient), wide(remainder)), (a / b, a % b));
+        }
+    }
+
+    #[test]
+    fn test_shift() {
+        let mut rng = Rng::new();
+
+        for _ in 0..1_000 {
+            let a = value(&mut rng);
+            for shift in [0, 1, 63, 64, 65, 127, rng.below(128) as usize] {
+                assert_eq!(wide(U128::from(a) << shift), a << shift);
+                assert_eq!(wide(U128::from(a) >> shift), a >> shift);
+            }
+            assert!((U128::from(a) << 128usize).is_zero());
+            assert!((U128::from(a) >> 128usize).is_zero());
+        }
+    }
+
+    #[test]
+    fn test_display_parse() {
+        let mut rng = Rng::new();
+
+        for a in [0, 1, 10_000_000_000_000_000_000, u64::MAX as u128 + 1, u128::MAX] {
+            assert_eq!(U128::from(a).to_string(), a.to_string());
+        }
+        for _ in 0..1_000 {
+            let a = value(&mut rng);
+            let x = U128::from(a);
+            assert_eq!(x.to_string(), a.to_string());
+            assert_eq!(format!("{x:x}"), format!("{a:x}"));
+            assert_eq!(format!("{x:#b}"), format!("{a:#b}"));
+            assert_eq!(a.to_string().parse(), Ok(x));
+            assert_eq!(format!("0x{a:X}").parse(), Ok(x));
+        }
+
+        assert_eq!("".parse::<U128>(), Err(ParseUX64Error::Empty));
+        assert_eq!("12a".parse::<U128>(), Err(ParseUX64Error::InvalidDigit));
+        assert_eq!(
+            "340282366920938463463374607431768211456".parse::<U128>(),
+            Err(ParseUX64Error::Overflow)
+        );
+    }
+}
@@ -0,0 +1,296 @@
+//! Combinatorial enumeration and counting.
+//!
+//! Like [`permutations`], the slice methods in [`CombinationOps`] reuse a single buffer and pass
+//! each arrangement to a callback, so nothing is allocated per item:
+//!
+//! * [`combinations`] picks `k` distinct elements in lexicographic order of their indices.
+//! * [`combinations_with_repetition`] picks `k` elements where each may be chosen many times.
+//!
+//! [`cartesian_product`] does the same for one element from each of several slices.
+//!
+//! Subsets of up to 63 elements are also available as `u64` bitmasks, which are `Copy` and can be
+//! returned from ordinary iterators:
+//!
+//! * [`bit_combinations`] yields every mask with exactly `k` bits set in increasing order, using
+//!   [Gosper's hack](https://en.wikipedia.org/wiki/Combinatorial_number_system#Applications).
+//! * [`gray_code`] walks the whole power set in [Gray code](https://en.wikipedia.org/wiki/Gray_code)
+//!   order along with the index that was toggled, so that state built up for one subset can be
+//!   updated in a single step for the next.
+//!
+//! [`binomial`] and [`multinomial`] count the arrangements without enumerating them.
+//!
+//! [`permutations`]: crate::maneatingape::slice::SliceOps::permutations
+//! [`combinations`]: CombinationOps::combinations
+//! [`combinations_with_repetition`]: CombinationOps::combinations_with_repetition
+pub trait CombinationOps<T> {
+    fn combinations(self, k: usize, callback: impl FnMut(&[T]));
+    fn combinations_with_repetition(self, k: usize, callback: impl FnMut(&[T]));
+}
+
+impl<T: Copy> CombinationOps<T> for &[T] {
+    fn combinations(self, k: usize, mut callback: impl FnMut(&[T])) {
+        let n = self.len();
+        if k > n {
+            return;
+        }
+
+        let mut indices: Vec<_> = (0..k).collect();
+        let mut buffer: Vec<_> = self[..k].to_vec();
+
+        loop {
+            callback(&buffer);
+
+            // Advance the rightmost index that still has room, then reset the ones after it.
+            let Some(i) = (0..k).rev().find(|&i| indices[i] < n - k + i) else {
+                return;
+            };
+            indices[i] += 1;
+            for j in i..k {
+                indices[j] = indices[i] + j - i;
+                buffer[j] = self[indices[j]];
+            }
+        }
+    }
+
+    fn combinations_with_repetition(self, k: usize, mut callback: impl FnMut(&[T])) {
+        let n = self.len();
+        if n == 0 {
+            if k == 0 {
+                callback(&[]);
+            }
+            return;
+        }
+
+        let mut indices = vec![0; k];
+        let mut buffer = vec![self[0]; k];
+
+        loop {
+            callback(&buffer);
+
+            // Indices never decrease, so everything after the advanced one restarts from it.
+            let Some(i) = (0..k).rev().find(|&i| indices[i] < n - 1) else {
+                return;
+            };
+            indices[i] += 1;
+            for j in i..k {
+                indices[j] = indices[i];
+                buffer[j] = self[indices[i]];
+            }
+        }
+    }
+}
+
+/// Passes every way of choosing one element from each slice to `callback`, varying the last
+/// slice fastest.
+pub fn cartesian_product<T: Copy>(slices: &[&[T]], mut callback: impl FnMut(&[T])) {
+    if slices.iter().any(|slice| slice.is_empty()) {
+        return;
+    }
+
+    let mut indices = vec![0; slices.len()];
+    let mut buffer: Vec<_> = slices.iter().map(|slice| slice[0]).collect();
+
+    loop {
+        callback(&buffer);
+
+        // Odometer increment, wrapping exhausted positions back to their first element.
+        let Some(i) = (0..slices.len()).rev().find(|&i| indices[i] + 1 < slices[i].len()) else {
+            return;
+        };
+        indices[i] += 1;
+        buffer[i] = slices[i][indices[i]];
+        for j in i + 1..slices.len() {
+            indices[j] = 0;
+            buffer[j] = slices[j][0];
+        }
+    }
+}
+
+/// Every subset of `n` elements with exactly `k` members as a bitmask, in increasing order.
+pub fn bit_combinations(n: u32, k: u32) -> BitCombinations {
+    assert!(n < 64, "At most 63 elements are supported");
+    let next = if k <= n { Some((1 << k) - 1) } else { None };
+    BitCombinations { next, limit: 1 << n }
+}
+
+pub struct BitCombinations {
+    next: Option<u64>,
+    limit: u64,
+}
+
+impl Iterator for BitCombinations {
+    type Item = u64;
+
+    #[inline]
+    fn next(&mut self) -> Option<u64> {
+        let current = self.next?;
+        self.next = if current == 0 {
+            None
+        } else {
+            let lowest = current & current.wrapping_neg();
+            let ripple = current + lowest;
+            let next = (((ripple ^ current) >> 2) / lowest) | ripple;
+            (next < self.limit).then_some(next)
+        };
+        Some(current)
+    }
+}
+
+/// All `2ⁿ` subsets of `n` elements as bitmasks, starting with the empty set. Every later subset
+/// differs from the previous one in a single element, which is returned alongside it.
+pub fn gray_code(n: u32) -> GrayCode {
+    assert!(n < 64, "At most 63 elements are supported");
+    GrayCode { step: 0, count: 1 << n }
+}
+
+pub struct GrayCode {
+    step: u64,
+    count: u64,
+}
+
+impl Iterator for GrayCode {
+    type Item = (u64, Option<usize>);
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.count - self.step) as usize;
+        (remaining, Some(remaining))
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.step == self.count {
+            return None;
+        }
+
+        let step = self.step;
+        self.step += 1;
+        let toggled = (step > 0).then(|| step.trailing_zeros() as usize);
+        Some((step ^ (step >> 1), toggled))
+    }
+}
+
+/// Number of ways to choose `k` elements out of `n`.
+///
+/// Panics if the result does not fit in a `u64`.
+pub fn binomial(n: u64, k: u64) -> u64 {
+    if k > n {
+        return 0;
+    }
+
+    // Each partial product is itself a binomial coefficient, so the division is always exact.
+    (0..k.min(n - k)).fold(1, |result: u64, i| {
+        let next = result as u128 * (n - i) as u128 / (i + 1) as u128;
+        u64::try_from(next).expect("Binomial coefficient overflows u64")
+    })
+}
+
+/// Number of ways to arrange a multiset with the given number of copies of each element.
+///
+/// Panics if the result does not fit in a `u64`.
+pub fn multinomial(counts: &[u64]) -> u64 {
+    let mut total = 0;
+    counts.iter().fold(1, |result: u64, &count| {
+        total += count;
+        result.checked_mul(binomial(total, count)).expect("Multinomial coefficient overflows u64")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_combinations() {
+        for n in 0..=7 {
+            let items: Vec<_> = (0..n).collect();
+            for k in 0..=n + 1 {
+                let mut all = Vec::new();
+                items.as_slice().combinations(k, |c| all.push(c.to_vec()));
+                assert_eq!(all.len() as u64, binomial(n as u64, k as u64));
+                assert!(all.iter().all(|c| c.len() == k && c.is_sorted_by(|a, b| a < b)));
+                assert!(all.is_sorted_by(|a, b| a < b));
+
+                let mut all = Vec::new();
+                items.as_slice().combinations_with_repetition(k, |c| all.push(c.to_vec()));
+                let expected =
+                    if n == 0 { (k == 0) as u64 } else { binomial((n + k - 1) as u64, k as u64) };
+                assert_eq!(all.len() as u64, expected);
+                assert!(all.iter().all(|c| c.len() == k && c.is_sorted()));
+                assert!(all.is_sorted_by(|a, b| a < b));
+            }
+        }
+
+        let mut all = Vec::new();
+        [1, 2, 3].as_slice().combinations(2, |c| all.push(c.to_vec()));
+        assert_eq!(all, [[1, 2], [1, 3], [2, 3]]);
+    }
+
+    #[test]
+    fn test_cartesian_product() {
+        let mut all = Vec::new();
+        cartesian_product(&[&[1, 2], &[3], &[4, 5]], |p| all.push(p.to_vec()));
+        assert_eq!(all, [[1, 3, 4], [1, 3, 5], [2, 3, 4], [2, 3, 5]]);
+
+        let mut count = 0;
+        cartesian_product(&[&[1, 2], &[]], |_| count += 1);
+        assert_eq!(count, 0);
+        cartesian_product::<u8>(&[], |p| {
+            assert!(p.is_empty());
+            count += 1;
+        });
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_bit_combinations() {
+        for n in 0..=10 {
+            for k in 0..=n + 1 {
+                let masks: Vec<_> = bit_combinations(n, k).collect();
+                assert_eq!(masks.len() as u64, binomial(n as u64, k as u64));
+                assert!(masks.iter().all(|mask| mask.count_ones() == k && mask >> n == 0));
+                assert!(masks.is_sorted_by(|a, b| a < b));
+            }
+        }
+
+        assert_eq!(bit_combinations(63, 0).collect::<Vec<_>>(), [0]);
+        assert_eq!(bit_combinations(63, 1).count(), 63);
+        assert_eq!(bit_combinations(63, 1).last(), Some(1 << 62));
+        assert_eq!(bit_combinations(63, 62).count(), 63);
+        assert_eq!(bit_combinations(63, 63).collect::<Vec<_>>(), [u64::MAX >> 1]);
+        assert_eq!(bit_combinations(63, 64).count(), 0);
+    }
+
+    #[test]
+    fn test_gray_code() {
+        assert_eq!(gray_code(0).collect::<Vec<_>>(), [(0, None)]);
+
+        let codes: Vec<_> = gray_code(6).collect();
+        assert_eq!(codes.len(), 64);
+        let mut seen: Vec<_> = codes.iter().map(|&(mask, _)| mask).collect();
+        seen.sort_unstable();
+        assert!(seen.iter().copied().eq(0..64));
+        for pair in codes.windows(2) {
+            let ((previous, _), (mask, toggled)) = (pair[0], pair[1]);
+            assert_eq!(previous ^ mask, 1 << toggled.unwrap());
+        }
+        assert_eq!(gray_code(63).size_hint(), (1 << 63, Some(1 << 63)));
+    }
+
+    #[test]
+    fn test_binomial() {
+        assert_eq!(binomial(5, 0), 1);
+        assert_eq!(binomial(5, 5), 1);
+        assert_eq!(binomial(5, 6), 0);
+        assert_eq!(binomial(0, 0), 1);
+        assert_eq!(binomial(67, 33), 14_226_520_737_620_288_370);
+        assert_eq!(multinomial(&[2, 1, 1]), 12);
+        assert_eq!(multinomial(&[]), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Binomial coefficient overflows u64")]
+    fn test_binomial_overflow() {
+        binomial(68, 34);
+    }
+}
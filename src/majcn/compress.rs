@@ -0,0 +1,279 @@
+//! [Coordinate compression](https://en.wikipedia.org/wiki/Coordinate_compression) for puzzles
+//! whose coordinates are far too large for a dense [`Grid`].
+//!
+//! A [`Compressor`] maps each distinct coordinate to a dense index and back. It also splits the
+//! whole axis into cells: one cell for every coordinate, one for every gap between two
+//! consecutive coordinates, and an unbounded cell at each end. For coordinates `2, 5, 6` the
+//! cells are:
+//!
+//! ```none
+//!   cell    0     1    2     3    4    5    6
+//!   range  ..2    2   3..5   5    -    6   7..
+//!   size    0     1    2     1    0    1    0
+//! ```
+//!
+//! Because edges only ever lie on the coordinates themselves, anything that is decided by those
+//! edges (inside or outside a polygon, reachable or not) is the same for every point of a cell.
+//! The outer cells are unbounded, so they report a size of zero. This keeps them out of area
+//! calculations, while still giving flood fills a ring that connects all of the outside.
+//!
+//! A [`CompressedGrid`] has one cell for every pair of x and y cells, and reports the real width,
+//! height and area of each one. [`PrefixSums`] answers sums over any rectangle of cells in
+//! constant time. With [`PrefixSums::areas`] each cell is weighted by its real area.
+use crate::maneatingape::grid::*;
+use crate::maneatingape::point::*;
+use std::ops::{Index, IndexMut};
+
+#[derive(Clone, Debug)]
+pub struct Compressor<T> {
+    values: Vec<T>,
+}
+
+impl<T: Coordinate + Into<i64>> Compressor<T> {
+    pub fn new(values: impl IntoIterator<Item = T>) -> Self {
+        let mut values: Vec<_> = values.into_iter().collect();
+        values.sort_unstable();
+        values.dedup();
+        Compressor { values }
+    }
+
+    /// Number of distinct coordinates.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Distinct coordinates in increasing order.
+    #[inline]
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Dense index of `value`, if it is one of the coordinates.
+    #[inline]
+    pub fn index(&self, value: T) -> Option<usize> {
+        self.values.binary_search(&value).ok()
+    }
+
+    #[inline]
+    pub fn value(&self, index: usize) -> T {
+        self.values[index]
+    }
+
+    /// Number of cells along the axis, including both unbounded ones.
+    #[inline]
+    pub fn cells(&self) -> usize {
+        2 * self.values.len() + 1
+    }
+
+    /// Cell that contains `value`.
+    #[inline]
+    pub fn cell(&self, value: T) -> usize {
+        match self.values.binary_search(&value) {
+            Ok(i) => 2 * i + 1,
+            Err(i) => 2 * i,
+        }
+    }
+
+    /// Number of integer coordinates covered by `cell`, zero for the unbounded ends.
+    #[inline]
+    pub fn cell_size(&self, cell: usize) -> i64 {
+        if cell % 2 == 1 {
+            1
+        } else if cell == 0 || cell == 2 * self.values.len() {
+            0
+        } else {
+            let i = cell / 2;
+            self.values[i].into() - self.values[i - 1].into() - 1
+        }
+    }
+}
+
+/// Grid with one cell per pair of `xs` and `ys` cells, which knows the real size of each cell.
+#[derive(Clone, Debug)]
+pub struct CompressedGrid<T, C> {
+    pub grid: Grid<T>,
+    pub xs: Compressor<C>,
+    pub ys: Compressor<C>,
+}
+
+impl<T: Copy, C: Coordinate + Into<i64>> CompressedGrid<T, C> {
+    pub fn new(xs: Compressor<C>, ys: Compressor<C>, default: T) -> Self {
+        let grid = Grid::new(xs.cells() as i32, ys.cells() as i32, default);
+        CompressedGrid { grid, xs, ys }
+    }
+
+    /// Cell that contains `point`.
+    #[inline]
+    pub fn cell(&self, point: Point<C>) -> Point {
+        Point::new(self.xs.cell(point.x) as i32, self.ys.cell(point.y) as i32)
+    }
+
+    /// Real width of the cells in column `x`.
+    #[inline]
+    pub fn width(&self, x: i32) -> i64 {
+        self.xs.cell_size(x as usize)
+    }
+
+    /// Real height of the cells in row `y`.
+    #[inline]
+    pub fn height(&self, y: i32) -> i64 {
+        self.ys.cell_size(y as usize)
+    }
+
+    /// Number of integer points covered by the cell at `point`.
+    #[inline]
+    pub fn area(&self, point: Point) -> i64 {
+        self.width(point.x) * self.height(point.y)
+    }
+}
+
+impl<T, C> Index<Point> for CompressedGrid<T, C> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: Point) -> &Self::Output {
+        &self.grid[index]
+    }
+}
+
+impl<T, C> IndexMut<Point> for CompressedGrid<T, C> {
+    #[inline]
+    fn index_mut(&mut self, index: Point) -> &mut Self::Output {
+        &mut self.grid[index]
+    }
+}
+
+pub struct PrefixSums {
+    width: usize,
+    sums: Vec<i64>,
+}
+
+impl PrefixSums {
+    pub fn new<T: Copy>(grid: &Grid<T>, mut weight: impl FnMut(Point, T) -> i64) -> Self {
+        let width = grid.width as usize + 1;
+        let mut sums = vec![0; width * (grid.height as usize + 1)];
+
+        for y in 0..grid.height {
+            for x in 0..grid.width {
+                let point = Point::new(x, y);
+                let (x, y) = (x as usize, y as usize);
+                sums[(y + 1) * width + x + 1] = weight(point, grid[point])
+                    + sums[y * width + x + 1]
+                    + sums[(y + 1) * width + x]
+                    - sums[y * width + x];
+            }
+        }
+
+        PrefixSums { width, sums }
+    }
+
+    /// Sums `weight` multiplied by the real area of each cell of a compressed grid.
+    pub fn areas<T: Copy, C: Coordinate + Into<i64>>(
+        grid: &CompressedGrid<T, C>,
+        mut weight: impl FnMut(Point, T) -> i64,
+    ) -> Self {
+        Self::new(&grid.grid, |point, value| {
+            let area = grid.area(point);
+            if area == 0 { 0 } else { weight(point, value) * area }
+        })
+    }
+
+    /// Sum over the cells from `min` to `max` inclusive.
+    #[inline]
+    pub fn sum(&self, min: Point, max: Point) -> i64 {
+        let (x1, y1) = (min.x as usize, min.y as usize);
+        let (x2, y2) = (max.x as usize + 1, max.y as usize + 1);
+        let w = self.width;
+        self.sums[y2 * w + x2] - self.sums[y1 * w + x2] - self.sums[y2 * w + x1]
+            + self.sums[y1 * w + x1]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::majcn::grid::*;
+    use crate::majcn::testing::Rng;
+
+    #[test]
+    fn test_compressor() {
+        let xs = Compressor::new([6, 2, 5, 2]);
+
+        assert_eq!(xs.values(), [2, 5, 6]);
+        assert_eq!((xs.len(), xs.cells()), (3, 7));
+        assert_eq!((xs.index(5), xs.index(4), xs.value(2)), (Some(1), None, 6));
+        assert_eq!([0, 2, 3, 4, 5, 6, 7].map(|v| xs.cell(v)), [0, 1, 2, 2, 3, 5, 6]);
+        assert_eq!((0..7).map(|c| xs.cell_size(c)).collect::<Vec<_>>(), [0, 1, 2, 1, 0, 1, 0]);
+
+        let empty = Compressor::<i32>::new([]);
+        assert_eq!((empty.cells(), empty.cell(7), empty.cell_size(0)), (1, 0, 0));
+    }
+
+    #[test]
+    fn test_cell_size() {
+        let mut rng = Rng::new();
+
+        for _ in 0..100 {
+            let xs = Compressor::new((0..rng.below(6) + 1).map(|_| rng.below(30) as i64));
+            let (&min, &max) = (xs.values().first().unwrap(), xs.values().last().unwrap());
+
+            let mut sizes = vec![0; xs.cells()];
+            (min..=max).for_each(|v| sizes[xs.cell(v)] += 1);
+            assert!((0..xs.cells()).all(|c| xs.cell_size(c) == sizes[c]));
+        }
+    }
+
+    #[test]
+    fn test_areas() {
+        let mut rng = Rng::new();
+
+        for _ in 0..50 {
+            let coordinates = |rng: &mut Rng| {
+                Compressor::new((0..rng.below(5) + 1).map(|_| rng.below(20) as i32))
+            };
+            let xs = coordinates(&mut rng);
+            let ys = coordinates(&mut rng);
+
+            let mut compressed = CompressedGrid::new(xs, ys, 0);
+            for point in compressed.grid.points() {
+                compressed[point] = rng.below(10) as i64;
+            }
+
+            // Every integer point inside the bounding box, with the cell that it falls in.
+            let dense: Vec<_> = (0..20)
+                .flat_map(|y| (0..20).map(move |x| Point::new(x, y)))
+                .map(|p| compressed.cell(p))
+                .filter(|c| compressed.area(*c) > 0)
+                .collect();
+            for point in compressed.grid.points() {
+                let count = dense.iter().filter(|&&c| c == point).count() as i64;
+                assert_eq!(compressed.area(point), count);
+            }
+
+            let sums = PrefixSums::areas(&compressed, |_, value| value);
+            for _ in 0..20 {
+                let mut cell = || {
+                    let x = rng.below(compressed.grid.width as u64) as i32;
+                    let y = rng.below(compressed.grid.height as u64) as i32;
+                    Point::new(x, y)
+                };
+                let (a, b) = (cell(), cell());
+                let min = Point::new(a.x.min(b.x), a.y.min(b.y));
+                let max = Point::new(a.x.max(b.x), a.y.max(b.y));
+
+                let expected: i64 = dense
+                    .iter()
+                    .filter(|c| min.x <= c.x && c.x <= max.x && min.y <= c.y && c.y <= max.y)
+                    .map(|&c| compressed[c])
+                    .sum();
+                assert_eq!(sums.sum(min, max), expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,188 @@
+//! [Cycle detection](https://en.wikipedia.org/wiki/Cycle_detection) for simulations that are
+//! asked to run for far more steps than is feasible, such as a billion spin cycles.
+//!
+//! A deterministic simulation eventually repeats a state, after which everything repeats. The
+//! [`Cycle`] records the first step of the loop and its length, which maps any step `n` back to
+//! an earlier step with the same state.
+//!
+//! * [`brent`] and [`floyd`] need only a constant number of `Copy` states, at the cost of
+//!   simulating some steps more than once. Brent's algorithm usually needs fewer steps.
+//! * [`hashed`] remembers every state in a [`FastMap`], so each step is simulated exactly once
+//!   and the state at any step can be looked up afterwards without simulating again. States only
+//!   need to be `Clone + Hash`, which includes a [`Grid`].
+//!
+//! [`Grid`]: crate::maneatingape::grid::Grid
+use crate::maneatingape::hash::*;
+use std::hash::Hash;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    /// First step that is part of the loop.
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Earliest step with the same state as step `n`.
+    #[inline]
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start { n } else { self.start + (n - self.start) % self.length }
+    }
+
+    /// State after `n` steps, simulating at most `start + length` of them.
+    pub fn nth<S>(&self, initial: S, mut next: impl FnMut(S) -> S, n: usize) -> S {
+        (0..self.reduce(n)).fold(initial, |state, _| next(state))
+    }
+}
+
+/// Brent's algorithm, which finds the length first by teleporting the tortoise to the hare
+/// whenever the distance between them reaches the next power of two.
+pub fn brent<S: Copy + Eq>(initial: S, mut next: impl FnMut(S) -> S) -> Cycle {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial;
+    let mut hare = next(initial);
+
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare;
+            power *= 2;
+            length = 0;
+        }
+        hare = next(hare);
+        length += 1;
+    }
+
+    Cycle { start: find_start(initial, &mut next, length), length }
+}
+
+/// Floyd's algorithm, with a hare moving twice as fast as the tortoise.
+pub fn floyd<S: Copy + Eq>(initial: S, mut next: impl FnMut(S) -> S) -> Cycle {
+    let mut tortoise = next(initial);
+    let mut hare = next(tortoise);
+
+    while tortoise != hare {
+        tortoise = next(tortoise);
+        hare = next(hare);
+        hare = next(hare);
+    }
+
+    // The tortoise has now moved a multiple of the length, so step once more around the loop.
+    let mut length = 1;
+    hare = next(tortoise);
+    while tortoise != hare {
+        hare = next(hare);
+        length += 1;
+    }
+
+    Cycle { start: find_start(initial, &mut next, length), length }
+}
+
+/// Two pointers `length` apart meet at the first step of the loop.
+fn find_start<S: Copy + Eq>(initial: S, next: &mut impl FnMut(S) -> S, length: usize) -> usize {
+    let mut tortoise = initial;
+    let mut hare = (0..length).fold(initial, |state, _| next(state));
+    let mut start = 0;
+
+    while tortoise != hare {
+        tortoise = next(tortoise);
+        hare = next(hare);
+        start += 1;
+    }
+
+    start
+}
+
+/// Simulates until a state repeats, keeping every state along the way.
+pub fn hashed<S: Clone + Eq + Hash>(initial: S, mut next: impl FnMut(&S) -> S) -> CycleStates<S> {
+    let mut seen = FastMap::new();
+    let mut states = vec![initial];
+
+    loop {
+        let step = states.len() - 1;
+        if let Some(start) = seen.insert(states[step].clone(), step) {
+            states.pop();
+            let cycle = Cycle { start, length: step - start };
+            return CycleStates { cycle, states };
+        }
+        states.push(next(&states[step]));
+    }
+}
+
+pub struct CycleStates<S> {
+    pub cycle: Cycle,
+    states: Vec<S>,
+}
+
+impl<S> CycleStates<S> {
+    /// State after `n` steps.
+    #[inline]
+    pub fn nth(&self, n: usize) -> &S {
+        &self.states[self.cycle.reduce(n)]
+    }
+
+    /// Every distinct state, in the order they were first reached.
+    #[inline]
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maneatingape::grid::*;
+    use crate::maneatingape::point::*;
+
+    fn step(x: u32) -> u32 {
+        (x * x + 1) % 255
+    }
+
+    #[test]
+    fn test_algorithms_agree() {
+        for initial in 0..255 {
+            let cycle = brent(initial, step);
+            assert_eq!(floyd(initial, step), cycle);
+            let states = hashed(initial, |&x| step(x));
+            assert_eq!(states.cycle, cycle);
+
+            let sequence: Vec<_> = std::iter::successors(Some(initial), |&x| Some(step(x)))
+                .take(cycle.start + 3 * cycle.length)
+                .collect();
+            for (n, &state) in sequence.iter().enumerate() {
+                assert_eq!(*states.nth(n), state);
+                assert_eq!(cycle.nth(initial, step, n), state);
+                assert_eq!(sequence[cycle.reduce(n)], state);
+            }
+            assert!(
+                cycle.start == 0
+                    || sequence[cycle.start - 1] != sequence[cycle.start + cycle.length - 1]
+            );
+        }
+
+        // 3 → 10 → 101 → 2 → 5 → 26 → 167 → 95 → 101, so the tail is two steps long.
+        assert_eq!(brent(3, step), Cycle { start: 2, length: 6 });
+    }
+
+    #[test]
+    fn test_hashed_grid() {
+        // The top row rotates with period 5, while the bottom row fills up over the first 5 steps.
+        let initial = Grid::parse("#.#..\n.....");
+        let next = |grid: &Grid<u8>| {
+            let mut next = grid.clone();
+            for x in 0..grid.width {
+                next[Point::new((x + 1) % grid.width, 0)] = grid[Point::new(x, 0)];
+            }
+            if let Some(x) = (0..grid.width).find(|&x| grid[Point::new(x, 1)] == b'.') {
+                next[Point::new(x, 1)] = b'#';
+            }
+            next
+        };
+
+        let states = hashed(initial.clone(), next);
+        assert_eq!(states.cycle, Cycle { start: 5, length: 5 });
+        assert_eq!(states.states().len(), 10);
+        assert_eq!(states.nth(1_000_000_002), &Grid::parse("..#.#\n#####"));
+        assert_eq!(states.cycle.nth(initial, |grid| next(&grid), 7), *states.nth(7));
+    }
+}
@@ -1,23 +1,356 @@
+//! Compass directions as enums, so that turning and table lookups need no `unreachable!()` arms.
+//!
+//! [`Dir4`] holds the orthogonal directions and [`Dir8`] adds the diagonals. Both are ordered
+//! clockwise starting at up, convert into a [`Point`] and can be used to index a
+//! `[T; 4]` or `[T; 8]` directly. Parsing accepts arrows `^v<>`, the letters `UDLR` and `NSEW`
+//! and compass words such as `north` or `south-east`, returning a [`ParseDirectionError`] for
+//! anything else.
 use crate::maneatingape::point::*;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
 
-#[inline]
-pub fn direction_to_index(direction: Point) -> usize {
-    match direction {
-        LEFT => 0,
-        RIGHT => 1,
-        UP => 2,
-        DOWN => 3,
-        _ => unreachable!(),
-    }
-}
-
-#[inline]
-pub fn index_to_direction(index: usize) -> Point {
-    match index {
-        0 => LEFT,
-        1 => RIGHT,
-        2 => UP,
-        3 => DOWN,
-        _ => unreachable!(),
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+/// An error which can be returned when parsing a [`Dir4`] or [`Dir8`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseDirectionError;
+
+impl Error for ParseDirectionError {}
+
+impl Display for ParseDirectionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a direction such as ^, U, N or north")
+    }
+}
+
+impl Dir4 {
+    pub const ALL: [Dir4; 4] = [Dir4::Up, Dir4::Right, Dir4::Down, Dir4::Left];
+
+    #[inline]
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    #[inline]
+    pub const fn from_index(index: usize) -> Self {
+        Self::ALL[index % 4]
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn turn_right(self) -> Self {
+        Self::from_index(self.index() + 1)
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn turn_left(self) -> Self {
+        Self::from_index(self.index() + 3)
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn turn_around(self) -> Self {
+        Self::from_index(self.index() + 2)
+    }
+
+    #[inline]
+    pub const fn point(self) -> Point {
+        match self {
+            Dir4::Up => UP,
+            Dir4::Right => RIGHT,
+            Dir4::Down => DOWN,
+            Dir4::Left => LEFT,
+        }
+    }
+}
+
+impl Dir8 {
+    pub const ALL: [Dir8; 8] =
+        [Dir8::N, Dir8::NE, Dir8::E, Dir8::SE, Dir8::S, Dir8::SW, Dir8::W, Dir8::NW];
+
+    #[inline]
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    #[inline]
+    pub const fn from_index(index: usize) -> Self {
+        Self::ALL[index % 8]
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn turn_right(self) -> Self {
+        Self::from_index(self.index() + 2)
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn turn_left(self) -> Self {
+        Self::from_index(self.index() + 6)
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn turn_around(self) -> Self {
+        Self::from_index(self.index() + 4)
+    }
+
+    /// Turns 45 degrees clockwise.
+    #[inline]
+    #[must_use]
+    pub const fn turn_half_right(self) -> Self {
+        Self::from_index(self.index() + 1)
+    }
+
+    /// Turns 45 degrees counter clockwise.
+    #[inline]
+    #[must_use]
+    pub const fn turn_half_left(self) -> Self {
+        Self::from_index(self.index() + 7)
+    }
+
+    #[inline]
+    pub const fn point(self) -> Point {
+        match self {
+            Dir8::N => UP,
+            Dir8::NE => Point::new(1, -1),
+            Dir8::E => RIGHT,
+            Dir8::SE => Point::new(1, 1),
+            Dir8::S => DOWN,
+            Dir8::SW => Point::new(-1, 1),
+            Dir8::W => LEFT,
+            Dir8::NW => Point::new(-1, -1),
+        }
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    #[inline]
+    fn from(value: Dir4) -> Self {
+        Dir8::from_index(value.index() * 2)
+    }
+}
+
+impl From<Dir4> for Point {
+    #[inline]
+    fn from(value: Dir4) -> Self {
+        value.point()
+    }
+}
+
+impl From<Dir8> for Point {
+    #[inline]
+    fn from(value: Dir8) -> Self {
+        value.point()
+    }
+}
+
+impl TryFrom<Point> for Dir4 {
+    type Error = ParseDirectionError;
+
+    #[inline]
+    fn try_from(value: Point) -> Result<Self, Self::Error> {
+        Dir4::ALL.into_iter().find(|d| d.point() == value).ok_or(ParseDirectionError)
+    }
+}
+
+impl TryFrom<Point> for Dir8 {
+    type Error = ParseDirectionError;
+
+    #[inline]
+    fn try_from(value: Point) -> Result<Self, Self::Error> {
+        Dir8::ALL.into_iter().find(|d| d.point() == value).ok_or(ParseDirectionError)
+    }
+}
+
+impl TryFrom<u8> for Dir4 {
+    type Error = ParseDirectionError;
+
+    #[inline]
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            b'^' | b'U' | b'u' | b'N' | b'n' => Ok(Dir4::Up),
+            b'>' | b'R' | b'r' | b'E' | b'e' => Ok(Dir4::Right),
+            b'v' | b'D' | b'd' | b'S' | b's' => Ok(Dir4::Down),
+            b'<' | b'L' | b'l' | b'W' | b'w' => Ok(Dir4::Left),
+            _ => Err(ParseDirectionError),
+        }
+    }
+}
+
+impl TryFrom<u8> for Dir8 {
+    type Error = ParseDirectionError;
+
+    #[inline]
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Dir4::try_from(value).map(Dir8::from)
+    }
+}
+
+impl TryFrom<u8> for Point {
+    type Error = ParseDirectionError;
+
+    #[inline]
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Dir4::try_from(value).map(Dir4::point)
+    }
+}
+
+impl FromStr for Dir4 {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match Dir8::from_str(s)? {
+            Dir8::N => Ok(Dir4::Up),
+            Dir8::E => Ok(Dir4::Right),
+            Dir8::S => Ok(Dir4::Down),
+            Dir8::W => Ok(Dir4::Left),
+            _ => Err(ParseDirectionError),
+        }
+    }
+}
+
+impl FromStr for Dir8 {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let [b] = s.as_bytes() {
+            return Dir8::try_from(*b);
+        }
+
+        let word: String = s
+            .chars()
+            .filter(|c| !matches!(c, '-' | '_' | ' '))
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+
+        match word.as_str() {
+            "up" | "north" => Ok(Dir8::N),
+            "ne" | "northeast" => Ok(Dir8::NE),
+            "right" | "east" => Ok(Dir8::E),
+            "se" | "southeast" => Ok(Dir8::SE),
+            "down" | "south" => Ok(Dir8::S),
+            "sw" | "southwest" => Ok(Dir8::SW),
+            "left" | "west" => Ok(Dir8::W),
+            "nw" | "northwest" => Ok(Dir8::NW),
+            _ => Err(ParseDirectionError),
+        }
+    }
+}
+
+impl<T> Index<Dir4> for [T; 4] {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: Dir4) -> &Self::Output {
+        &self[index.index()]
+    }
+}
+
+impl<T> IndexMut<Dir4> for [T; 4] {
+    #[inline]
+    fn index_mut(&mut self, index: Dir4) -> &mut Self::Output {
+        &mut self[index.index()]
+    }
+}
+
+impl<T> Index<Dir8> for [T; 8] {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: Dir8) -> &Self::Output {
+        &self[index.index()]
+    }
+}
+
+impl<T> IndexMut<Dir8> for [T; 8] {
+    #[inline]
+    fn index_mut(&mut self, index: Dir8) -> &mut Self::Output {
+        &mut self[index.index()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        for dir in Dir4::ALL {
+            assert_eq!((0..4).fold(dir, |d, _| d.turn_right()), dir);
+            assert_eq!((0..4).fold(dir, |d, _| d.turn_left()), dir);
+            assert_eq!(dir.turn_right().turn_left(), dir);
+            assert_eq!(dir.turn_around(), dir.turn_right().turn_right());
+            assert_eq!(dir.turn_right().point(), dir.point().clockwise());
+        }
+        for dir in Dir8::ALL {
+            assert_eq!((0..4).fold(dir, |d, _| d.turn_right()), dir);
+            assert_eq!((0..8).fold(dir, |d, _| d.turn_half_left()), dir);
+            assert_eq!(dir.turn_half_right().turn_half_right(), dir.turn_right());
+            assert_eq!(dir.turn_around().point(), -dir.point());
+        }
+        assert_eq!(Dir8::from(Dir4::Left), Dir8::W);
+    }
+
+    #[test]
+    fn test_conversions() {
+        for dir in Dir4::ALL {
+            assert_eq!(Dir4::try_from(dir.point()), Ok(dir));
+            assert_eq!(Point::from(dir), dir.point());
+        }
+        for dir in Dir8::ALL {
+            assert_eq!(Dir8::try_from(dir.point()), Ok(dir));
+        }
+
+        for (byte, dir) in b"^>v<".iter().zip(Dir4::ALL) {
+            assert_eq!(Dir4::try_from(*byte), Ok(dir));
+            assert_eq!(Point::try_from(*byte), Ok(dir.point()));
+            assert_eq!(Dir8::try_from(*byte), Ok(Dir8::from(dir)));
+        }
+        for (word, dir) in ["U", "e", "south", "West"].into_iter().zip(Dir4::ALL) {
+            assert_eq!(word.parse(), Ok(dir));
+        }
+        for (word, dir) in ["n", "NE", "east", "south-east", "S", "south west", "w", "north_west"]
+            .into_iter()
+            .zip(Dir8::ALL)
+        {
+            assert_eq!(word.parse(), Ok(dir));
+        }
+
+        let mut counts = [0; 4];
+        counts[Dir4::Down] += 1;
+        assert_eq!(counts, [0, 0, 1, 0]);
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(Dir4::try_from(b'x'), Err(ParseDirectionError));
+        assert_eq!(Dir4::try_from(Point::new(1, 1)), Err(ParseDirectionError));
+        assert_eq!(Dir8::try_from(Point::new(2, 0)), Err(ParseDirectionError));
+        assert_eq!("northeast".parse::<Dir4>(), Err(ParseDirectionError));
+        assert_eq!("".parse::<Dir8>(), Err(ParseDirectionError));
+        assert_eq!("upward".parse::<Dir8>(), Err(ParseDirectionError));
     }
 }
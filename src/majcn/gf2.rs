@@ -0,0 +1,312 @@
+//! Linear algebra over [GF(2)](https://en.wikipedia.org/wiki/GF(2)), where addition is xor.
+//!
+//! Each row of a [`Gf2Matrix`] is packed into a bitmask, so eliminating a row is a single xor.
+//! Any type implementing [`Bits`] can hold a row, which covers `u64`, `u128` and the wider
+//! [`UX64`].
+//!
+//! Solving `A·x = b` returns a [`Gf2Solution`], which describes every solution as one particular
+//! solution plus any combination of the nullspace basis. Puzzles that toggle lights with buttons
+//! usually ask for the solution that presses the fewest buttons, which is found with
+//! [`min_weight`] by walking all solutions in
+//! [Gray code](https://en.wikipedia.org/wiki/Gray_code) order, one xor per step.
+//!
+//! [`min_weight`]: Gf2Solution::min_weight
+use crate::majcn::bignumbers::*;
+use crate::majcn::combinatorics::*;
+
+pub trait Bits: Copy {
+    const ZERO: Self;
+
+    fn bit(self, index: usize) -> bool;
+    fn with_bit(self, index: usize) -> Self;
+    fn xor(self, other: Self) -> Self;
+    fn count_ones(self) -> u32;
+
+    #[inline]
+    fn is_zero(self) -> bool {
+        self.count_ones() == 0
+    }
+}
+
+macro_rules! bits {
+    ($($t:ty)*) => ($(
+        impl Bits for $t {
+            const ZERO: $t = 0;
+
+            #[inline]
+            fn bit(self, index: usize) -> bool {
+                self & (1 << index) != 0
+            }
+
+            #[inline]
+            fn with_bit(self, index: usize) -> Self {
+                self | (1 << index)
+            }
+
+            #[inline]
+            fn xor(self, other: Self) -> Self {
+                self ^ other
+            }
+
+            #[inline]
+            fn count_ones(self) -> u32 {
+                <$t>::count_ones(self)
+            }
+        }
+    )*)
+}
+
+bits!(u32 u64 u128);
+
+impl<const N: usize> Bits for UX64<N> {
+    const ZERO: Self = UX64::ZERO;
+
+    #[inline]
+    fn bit(self, index: usize) -> bool {
+        UX64::bit(&self, index)
+    }
+
+    #[inline]
+    fn with_bit(mut self, index: usize) -> Self {
+        self.set_bit(index, true);
+        self
+    }
+
+    #[inline]
+    fn xor(self, other: Self) -> Self {
+        self ^ other
+    }
+
+    #[inline]
+    fn count_ones(self) -> u32 {
+        UX64::count_ones(&self)
+    }
+
+    #[inline]
+    fn is_zero(self) -> bool {
+        UX64::is_zero(&self)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Gf2Matrix<B> {
+    rows: Vec<B>,
+    columns: usize,
+}
+
+impl<B: Bits> Gf2Matrix<B> {
+    /// Bit `j` of row `i` is the entry at row `i` and column `j`.
+    pub fn new(rows: Vec<B>, columns: usize) -> Self {
+        Gf2Matrix { rows, columns }
+    }
+
+    /// Builds the matrix from columns instead, where bit `i` of column `j` is the entry at
+    /// row `i` and column `j`.
+    pub fn from_columns(columns: &[B], rows: usize) -> Self {
+        let rows = (0..rows)
+            .map(|i| {
+                columns.iter().enumerate().fold(B::ZERO, |row, (j, column)| {
+                    if column.bit(i) { row.with_bit(j) } else { row }
+                })
+            })
+            .collect();
+        Gf2Matrix { rows, columns: columns.len() }
+    }
+
+    #[inline]
+    pub fn rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    #[inline]
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    pub fn rank(&self) -> usize {
+        self.eliminate(&vec![false; self.rows.len()]).pivots.len()
+    }
+
+    /// Basis of all `x` with `A·x = 0`.
+    pub fn nullspace(&self) -> Vec<B> {
+        self.eliminate(&vec![false; self.rows.len()]).nullspace(self.columns)
+    }
+
+    /// All `x` with `A·x = rhs`, or `None` if there are none.
+    pub fn solve(&self, rhs: &[bool]) -> Option<Gf2Solution<B>> {
+        let echelon = self.eliminate(rhs);
+        let rank = echelon.pivots.len();
+        if echelon.rhs[rank..].iter().any(|&b| b) {
+            return None;
+        }
+
+        let particular = echelon
+            .pivots
+            .iter()
+            .zip(&echelon.rhs)
+            .fold(B::ZERO, |x, (&p, &b)| if b { x.with_bit(p) } else { x });
+
+        Some(Gf2Solution { particular, nullspace: echelon.nullspace(self.columns) })
+    }
+
+    fn eliminate(&self, rhs: &[bool]) -> Echelon<B> {
+        let mut rows = self.rows.clone();
+        let mut rhs = rhs.to_vec();
+        let mut pivots = vec![];
+
+        for column in 0..self.columns {
+            let rank = pivots.len();
+            let Some(found) = (rank..rows.len()).find(|&r| rows[r].bit(column)) else {
+                continue;
+            };
+            rows.swap(rank, found);
+            rhs.swap(rank, found);
+
+            for r in 0..rows.len() {
+                if r != rank && rows[r].bit(column) {
+                    rows[r] = rows[r].xor(rows[rank]);
+                    rhs[r] ^= rhs[rank];
+                }
+            }
+
+            pivots.push(column);
+        }
+
+        Echelon { rows, rhs, pivots }
+    }
+}
+
+/// Reduced row echelon form along with the transformed right hand side.
+struct Echelon<B> {
+    rows: Vec<B>,
+    rhs: Vec<bool>,
+    pivots: Vec<usize>,
+}
+
+impl<B: Bits> Echelon<B> {
+    fn nullspace(&self, columns: usize) -> Vec<B> {
+        (0..columns)
+            .filter(|c| !self.pivots.contains(c))
+            .map(|free| {
+                self.rows.iter().zip(&self.pivots).fold(B::ZERO.with_bit(free), |v, (row, &p)| {
+                    if row.bit(free) { v.with_bit(p) } else { v }
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Gf2Solution<B> {
+    /// The solution where every free variable is zero.
+    pub particular: B,
+    pub nullspace: Vec<B>,
+}
+
+impl<B: Bits> Gf2Solution<B> {
+    /// Number of solutions is `2ⁿ` where `n` is the dimension of the nullspace.
+    #[inline]
+    pub fn dimension(&self) -> usize {
+        self.nullspace.len()
+    }
+
+    /// Every solution in Gray code order, starting with the particular solution.
+    pub fn solutions(&self) -> impl Iterator<Item = B> + '_ {
+        gray_code(self.nullspace.len() as u32).scan(self.particular, |x, (_, toggled)| {
+            if let Some(i) = toggled {
+                *x = x.xor(self.nullspace[i]);
+            }
+            Some(*x)
+        })
+    }
+
+    /// Solution with the fewest bits set.
+    pub fn min_weight(&self) -> B {
+        self.solutions().min_by_key(|x| x.count_ones()).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::majcn::testing::Rng;
+
+    type U128 = UX64<2>;
+
+    /// Every `x` with `A·x = rhs`, found by trying all of them.
+    fn brute_force(rows: &[u32], columns: usize, rhs: &[bool]) -> Vec<u32> {
+        (0..1 << columns)
+            .filter(|x| {
+                rows.iter().zip(rhs).all(|(row, &b)| (row & x).count_ones() & 1 == b as u32)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        let mut rng = Rng::new();
+
+        for _ in 0..300 {
+            let columns = rng.below(8) as usize + 1;
+            let rows: Vec<_> =
+                (0..rng.below(7) + 1).map(|_| rng.below(1 << columns) as u32).collect();
+            let rhs: Vec<_> = rows.iter().map(|_| rng.below(2) == 1).collect();
+            let matrix = Gf2Matrix::new(rows.clone(), columns);
+
+            let kernel = brute_force(&rows, columns, &vec![false; rows.len()]);
+            let nullspace = matrix.nullspace();
+            assert_eq!(1 << nullspace.len(), kernel.len());
+            assert_eq!(matrix.rank(), columns - nullspace.len());
+            assert!(nullspace.iter().all(|v| kernel.contains(v)));
+
+            let expected = brute_force(&rows, columns, &rhs);
+            let Some(solution) = matrix.solve(&rhs) else {
+                assert!(expected.is_empty());
+                continue;
+            };
+            let mut solutions: Vec<_> = solution.solutions().collect();
+            solutions.sort_unstable();
+            assert_eq!(solutions, expected);
+            assert_eq!(solution.dimension(), nullspace.len());
+
+            let min = expected.iter().map(|x| x.count_ones()).min().unwrap();
+            assert_eq!(solution.min_weight().count_ones(), min);
+
+            // Wide rows give the same answers.
+            let wide = Gf2Matrix::new(rows.iter().map(|&r| U128::from(r)).collect(), columns);
+            assert_eq!(wide.rank(), matrix.rank());
+            let wide = wide.solve(&rhs).unwrap();
+            assert_eq!(wide.particular, U128::from(solution.particular));
+            assert_eq!(wide.min_weight().count_ones(), min);
+        }
+    }
+
+    #[test]
+    fn test_systems() {
+        // x₀ + x₁ = 1 and x₀ + x₁ = 0 contradict each other.
+        let matrix = Gf2Matrix::new(vec![0b11u32, 0b11], 2);
+        assert_eq!(matrix.rank(), 1);
+        assert!(matrix.solve(&[true, false]).is_none());
+
+        // x₀ + x₁ = 1 with x₂ unconstrained leaves two free variables.
+        let matrix = Gf2Matrix::from_columns(&[1u32, 1, 0], 1);
+        let solution = matrix.solve(&[true]).unwrap();
+        assert_eq!(solution.dimension(), 2);
+        assert_eq!(solution.solutions().count(), 4);
+        assert_eq!(solution.min_weight().count_ones(), 1);
+    }
+
+    #[test]
+    fn test_wide_rows() {
+        // Row i is xᵢ + xᵢ₊₁, so the only non-zero vector in the nullspace is all ones.
+        let rows: Vec<_> = (0..99).map(|i| U128::ZERO.with_bit(i).with_bit(i + 1)).collect();
+        let matrix = Gf2Matrix::new(rows, 100);
+        assert_eq!(matrix.rank(), 99);
+        assert_eq!(matrix.nullspace(), [U128::MAX >> 28usize]);
+
+        let mut rhs = vec![false; 99];
+        rhs[98] = true;
+        let solution = matrix.solve(&rhs).unwrap();
+        assert_eq!(solution.min_weight(), U128::ZERO.with_bit(99));
+    }
+}
@@ -0,0 +1,385 @@
+//! Directed graphs whose nodes are named in the input, such as `aaa: bbb ccc`.
+//!
+//! [`Graph`] interns each name to a dense index on first sight and stores adjacency lists of
+//! indices, so algorithms work on plain `usize` and only the edges of the input touch the names.
+//!
+//! * [`topological_order`] uses [Kahn's algorithm](https://en.wikipedia.org/wiki/Topological_sorting#Kahn's_algorithm)
+//!   and doubles as cycle detection.
+//! * [`strongly_connected_components`] uses an iterative version of
+//!   [Tarjan's algorithm](https://en.wikipedia.org/wiki/Tarjan%27s_strongly_connected_components_algorithm),
+//!   so deep graphs can not overflow the stack.
+//! * [`count_paths`], [`longest_path`] and [`shortest_path`] relax each edge reachable from the
+//!   start once, in topological order. Only that reachable part has to be acyclic. Paths can be
+//!   required to pass through a set of nodes, which adds a bitmask of the required nodes seen so
+//!   far to each state.
+//!
+//! [`topological_order`]: Graph::topological_order
+//! [`strongly_connected_components`]: Graph::strongly_connected_components
+//! [`count_paths`]: Graph::count_paths
+//! [`longest_path`]: Graph::longest_path
+//! [`shortest_path`]: Graph::shortest_path
+use crate::maneatingape::hash::*;
+use std::borrow::Borrow;
+use std::hash::Hash;
+
+#[derive(Clone, Debug)]
+pub struct Graph<K> {
+    names: Vec<K>,
+    indices: FastMap<K, usize>,
+    edges: Vec<Vec<usize>>,
+}
+
+impl<K: Clone + Eq + Hash> Default for Graph<K> {
+    fn default() -> Self {
+        Graph::new()
+    }
+}
+
+impl<K: Clone + Eq + Hash> Graph<K> {
+    pub fn new() -> Self {
+        Graph { names: Vec::new(), indices: FastMap::new(), edges: Vec::new() }
+    }
+
+    /// Builds the graph from each node followed by the nodes it has edges to.
+    pub fn from_adjacency<I: IntoIterator<Item = K>>(
+        lists: impl IntoIterator<Item = (K, I)>,
+    ) -> Self {
+        let mut graph = Graph::new();
+        for (from, targets) in lists {
+            let from = graph.add_node(from);
+            for to in targets {
+                let to = graph.add_node(to);
+                graph.edges[from].push(to);
+            }
+        }
+        graph
+    }
+
+    /// Index of `name`, adding it as a new node without edges if it has not been seen before.
+    pub fn add_node(&mut self, name: K) -> usize {
+        if let Some(&index) = self.indices.get(&name) {
+            return index;
+        }
+        let index = self.names.len();
+        self.names.push(name.clone());
+        self.indices.insert(name, index);
+        self.edges.push(Vec::new());
+        index
+    }
+
+    pub fn add_edge(&mut self, from: K, to: K) {
+        let from = self.add_node(from);
+        let to = self.add_node(to);
+        self.edges[from].push(to);
+    }
+
+    #[inline]
+    pub fn index<Q: Eq + Hash + ?Sized>(&self, name: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+    {
+        self.indices.get(name).copied()
+    }
+
+    #[inline]
+    pub fn name(&self, index: usize) -> &K {
+        &self.names[index]
+    }
+}
+
+impl<K> Graph<K> {
+    /// Number of nodes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    #[inline]
+    pub fn neighbors(&self, index: usize) -> &[usize] {
+        &self.edges[index]
+    }
+
+    /// Every node ordered so that all edges point forwards, or `None` if there is a cycle.
+    pub fn topological_order(&self) -> Option<Vec<usize>> {
+        let mut in_degree = vec![0; self.len()];
+        self.edges.iter().flatten().for_each(|&to| in_degree[to] += 1);
+
+        let mut order: Vec<_> = (0..self.len()).filter(|&i| in_degree[i] == 0).collect();
+        let mut head = 0;
+
+        while let Some(&node) = order.get(head) {
+            head += 1;
+            for &next in &self.edges[node] {
+                in_degree[next] -= 1;
+                if in_degree[next] == 0 {
+                    order.push(next);
+                }
+            }
+        }
+
+        (order.len() == self.len()).then_some(order)
+    }
+
+    /// Nodes reachable from `from` ordered so that all edges between them point forwards.
+    ///
+    /// Panics if there is a cycle among them.
+    fn reachable_order(&self, from: usize) -> Vec<usize> {
+        let mut reachable = vec![false; self.len()];
+        let mut todo = vec![from];
+        reachable[from] = true;
+
+        while let Some(node) = todo.pop() {
+            for &next in &self.edges[node] {
+                if !reachable[next] {
+                    reachable[next] = true;
+                    todo.push(next);
+                }
+            }
+        }
+
+        let mut in_degree = vec![0; self.len()];
+        (0..self.len())
+            .filter(|&node| reachable[node])
+            .flat_map(|node| &self.edges[node])
+            .for_each(|&to| in_degree[to] += 1);
+
+        let mut order = Vec::new();
+        if in_degree[from] == 0 {
+            order.push(from);
+        }
+        let mut head = 0;
+
+        while let Some(&node) = order.get(head) {
+            head += 1;
+            for &next in &self.edges[node] {
+                in_degree[next] -= 1;
+                if in_degree[next] == 0 {
+                    order.push(next);
+                }
+            }
+        }
+
+        assert!(order.len() == reachable.iter().filter(|&&r| r).count(), "Graph must be acyclic");
+        order
+    }
+
+    #[inline]
+    pub fn has_cycle(&self) -> bool {
+        self.topological_order().is_none()
+    }
+
+    /// Groups of nodes that can all reach each other. Components are returned in reverse
+    /// topological order, so every edge leads to the same or an earlier component.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        let n = self.len();
+        let mut index = vec![usize::MAX; n];
+        let mut lowlink = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack = Vec::new();
+        let mut components = Vec::new();
+        let mut counter = 0;
+
+        // Each entry is a node along with the position of the next edge to explore.
+        let mut todo = Vec::new();
+
+        for root in 0..n {
+            if index[root] != usize::MAX {
+                continue;
+            }
+            todo.push((root, 0));
+
+            while let Some((node, edge)) = todo.pop() {
+                if edge == 0 {
+                    index[node] = counter;
+                    lowlink[node] = counter;
+                    counter += 1;
+                    stack.push(node);
+                    on_stack[node] = true;
+                }
+
+                if let Some(&next) = self.edges[node].get(edge) {
+                    todo.push((node, edge + 1));
+                    if index[next] == usize::MAX {
+                        todo.push((next, 0));
+                    } else if on_stack[next] {
+                        lowlink[node] = lowlink[node].min(index[next]);
+                    }
+                    continue;
+                }
+
+                if lowlink[node] == index[node] {
+                    let mut component = Vec::new();
+                    loop {
+                        let member = stack.pop().unwrap();
+                        on_stack[member] = false;
+                        component.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+                if let Some(&(parent, _)) = todo.last() {
+                    lowlink[parent] = lowlink[parent].min(lowlink[node]);
+                }
+            }
+        }
+
+        components
+    }
+
+    /// Number of paths from `from` to `to` that pass through every node in `required`.
+    ///
+    /// Panics if there is a cycle that can be reached from `from`.
+    pub fn count_paths(&self, from: usize, to: usize, required: &[usize]) -> u64 {
+        assert!(required.len() < 32, "At most 31 nodes can be required");
+        let order = self.reachable_order(from);
+
+        let masks = self.required_masks(required);
+        let states = 1 << required.len();
+        let mut ways = vec![0u64; self.len() * states];
+        ways[from * states + masks[from]] = 1;
+
+        for node in order {
+            for mask in 0..states {
+                let count = ways[node * states + mask];
+                if count == 0 {
+                    continue;
+                }
+                for &next in &self.edges[node] {
+                    ways[next * states + (mask | masks[next])] += count;
+                }
+            }
+        }
+
+        ways[to * states + states - 1]
+    }
+
+    /// Largest total `weight` of the edges along any path from `from` to `to`, or `None` if
+    /// `to` is unreachable.
+    ///
+    /// Panics if there is a cycle that can be reached from `from`.
+    pub fn longest_path(
+        &self,
+        from: usize,
+        to: usize,
+        weight: impl FnMut(usize, usize) -> i64,
+    ) -> Option<i64> {
+        self.best_path(from, to, weight, i64::max)
+    }
+
+    /// Smallest total `weight` of the edges along any path from `from` to `to`, or `None` if
+    /// `to` is unreachable. Unlike Dijkstra's algorithm, weights may be negative.
+    ///
+    /// Panics if there is a cycle that can be reached from `from`.
+    pub fn shortest_path(
+        &self,
+        from: usize,
+        to: usize,
+        weight: impl FnMut(usize, usize) -> i64,
+    ) -> Option<i64> {
+        self.best_path(from, to, weight, i64::min)
+    }
+
+    fn best_path(
+        &self,
+        from: usize,
+        to: usize,
+        mut weight: impl FnMut(usize, usize) -> i64,
+        better: fn(i64, i64) -> i64,
+    ) -> Option<i64> {
+        let order = self.reachable_order(from);
+
+        let mut best = vec![None; self.len()];
+        best[from] = Some(0);
+
+        for node in order {
+            let Some(distance) = best[node] else {
+                continue;
+            };
+            for &next in &self.edges[node] {
+                let candidate = distance + weight(node, next);
+                best[next] =
+                    Some(best[next].map_or(candidate, |current| better(current, candidate)));
+            }
+        }
+
+        best[to]
+    }
+
+    fn required_masks(&self, required: &[usize]) -> Vec<usize> {
+        let mut masks = vec![0; self.len()];
+        required.iter().enumerate().for_each(|(bit, &node)| masks[node] |= 1 << bit);
+        masks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(input: &str) -> Graph<&str> {
+        Graph::from_adjacency(input.lines().map(|line| {
+            let (from, targets) = line.split_once(": ").unwrap();
+            (from, targets.split_ascii_whitespace())
+        }))
+    }
+
+    fn names<'a>(graph: &Graph<&'a str>, nodes: &[usize]) -> Vec<&'a str> {
+        let mut names: Vec<_> = nodes.iter().map(|&node| *graph.name(node)).collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        let graph = graph("a: b\nb: c\nc: a d\nd: e\ne: d\nf: a");
+        assert!(graph.has_cycle());
+
+        let components: Vec<_> = graph
+            .strongly_connected_components()
+            .iter()
+            .map(|component| names(&graph, component))
+            .collect();
+        assert_eq!(components, [vec!["d", "e"], vec!["a", "b", "c"], vec!["f"]]);
+    }
+
+    #[test]
+    fn test_count_paths() {
+        let graph = graph(
+            "svr: aaa bbb\naaa: fft\nfft: ccc\nbbb: tty\ntty: ccc\nccc: ddd eee\nddd: hub\n\
+             hub: fff\neee: dac\ndac: fff\nfff: ggg hhh\nggg: out\nhhh: out",
+        );
+        let [svr, out, dac, fft] =
+            ["svr", "out", "dac", "fft"].map(|name| graph.index(name).unwrap());
+
+        assert_eq!(graph.count_paths(svr, out, &[]), 8);
+        assert_eq!(graph.count_paths(svr, out, &[dac, fft]), 2);
+        assert_eq!(graph.count_paths(out, svr, &[]), 0);
+        assert_eq!(names(&graph, graph.neighbors(svr)), ["aaa", "bbb"]);
+    }
+
+    #[test]
+    fn test_unreachable_cycle() {
+        let graph = graph("a: b c\nb: d\nc: d\nx: y\ny: x b");
+        let [a, b, d, x] = ["a", "b", "d", "x"].map(|name| graph.index(name).unwrap());
+
+        assert!(graph.has_cycle());
+        assert_eq!(graph.count_paths(a, d, &[]), 2);
+        assert_eq!(graph.count_paths(b, d, &[]), 1);
+        assert_eq!(graph.longest_path(a, d, |_, _| 1), Some(2));
+        assert_eq!(graph.shortest_path(a, x, |_, _| 1), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Graph must be acyclic")]
+    fn test_reachable_cycle() {
+        let graph = graph("a: b\nb: c\nc: b d");
+        graph.count_paths(0, graph.index("d").unwrap(), &[]);
+    }
+}
@@ -1,8 +1,13 @@
 use crate::maneatingape::grid::*;
 use crate::maneatingape::point::*;
+use std::slice::Iter;
 
-pub trait GridPointsIterator {
+pub trait GridPointsIterator<T> {
     fn points(&self) -> GridPointsIter;
+    fn points_where(&self, predicate: impl Fn(&T) -> bool) -> impl Iterator<Item = Point>;
+    fn points_with(&self, value: T) -> impl Iterator<Item = Point>
+    where
+        T: PartialEq;
 }
 
 pub struct GridPointsIter {
@@ -12,10 +17,21 @@ pub struct GridPointsIter {
     height: i32,
 }
 
-impl<T> GridPointsIterator for Grid<T> {
+impl<T> GridPointsIterator<T> for Grid<T> {
     fn points(&self) -> GridPointsIter {
         GridPointsIter { x: 0, y: 0, width: self.width, height: self.height }
     }
+
+    fn points_where(&self, predicate: impl Fn(&T) -> bool) -> impl Iterator<Item = Point> {
+        self.points().filter(move |&p| predicate(&self[p]))
+    }
+
+    fn points_with(&self, value: T) -> impl Iterator<Item = Point>
+    where
+        T: PartialEq,
+    {
+        self.points().filter(move |&p| self[p] == value)
+    }
 }
 
 impl Iterator for GridPointsIter {
@@ -27,7 +43,7 @@ impl Iterator for GridPointsIter {
             self.y += 1;
         }
 
-        if self.y == self.height {
+        if self.y >= self.height || self.width == 0 {
             return None;
         }
 
@@ -36,3 +52,189 @@ impl Iterator for GridPointsIter {
         Some(Point::new(self.x - 1, self.y))
     }
 }
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Topology {
+    /// Neighbors outside the grid are skipped.
+    Bounded,
+    /// Neighbors outside the grid wrap around to the opposite edge.
+    Wrapping,
+}
+
+pub trait GridNeighbors<T> {
+    fn get(&self, point: Point) -> Option<&T>;
+    fn neighbors<'a>(
+        &self,
+        point: Point,
+        stencil: &'a [Point],
+        topology: Topology,
+    ) -> Neighbors<'a>;
+    fn orthogonal_neighbors(&self, point: Point) -> Neighbors<'static>;
+    fn diagonal_neighbors(&self, point: Point) -> Neighbors<'static>;
+    fn neighbor_values(
+        &self,
+        point: Point,
+        stencil: &[Point],
+        padding: T,
+    ) -> impl Iterator<Item = T>;
+    fn padded(&self, border: i32, padding: T) -> Grid<T>;
+}
+
+pub struct Neighbors<'a> {
+    point: Point,
+    stencil: Iter<'a, Point>,
+    width: i32,
+    height: i32,
+    topology: Topology,
+}
+
+impl<T: Copy> GridNeighbors<T> for Grid<T> {
+    #[inline]
+    fn get(&self, point: Point) -> Option<&T> {
+        self.contains(point).then(|| &self[point])
+    }
+
+    #[inline]
+    fn neighbors<'a>(
+        &self,
+        point: Point,
+        stencil: &'a [Point],
+        topology: Topology,
+    ) -> Neighbors<'a> {
+        Neighbors {
+            point,
+            stencil: stencil.iter(),
+            width: self.width,
+            height: self.height,
+            topology,
+        }
+    }
+
+    #[inline]
+    fn orthogonal_neighbors(&self, point: Point) -> Neighbors<'static> {
+        self.neighbors(point, &ORTHOGONAL, Topology::Bounded)
+    }
+
+    #[inline]
+    fn diagonal_neighbors(&self, point: Point) -> Neighbors<'static> {
+        self.neighbors(point, &DIAGONAL, Topology::Bounded)
+    }
+
+    /// Values of all neighbors in `stencil`, using `padding` for those outside the grid.
+    #[inline]
+    fn neighbor_values(
+        &self,
+        point: Point,
+        stencil: &[Point],
+        padding: T,
+    ) -> impl Iterator<Item = T> {
+        stencil.iter().map(move |&d| self.get(point + d).copied().unwrap_or(padding))
+    }
+
+    /// Copy of the grid surrounded by a `border` cells wide frame of `padding`, so that
+    /// neighbors of the original cells can be indexed without bounds checks.
+    fn padded(&self, border: i32, padding: T) -> Grid<T> {
+        let mut grid = Grid::new(self.width + 2 * border, self.height + 2 * border, padding);
+        for y in 0..self.height {
+            let start = (grid.width * (y + border) + border) as usize;
+            grid.bytes[start..start + self.width as usize].copy_from_slice(self.row(y));
+        }
+        grid
+    }
+}
+
+impl Iterator for Neighbors<'_> {
+    type Item = Point;
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.stencil.size_hint().1)
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        for &d in self.stencil.by_ref() {
+            let n = self.point + d;
+            match self.topology {
+                Topology::Bounded => {
+                    if n.x >= 0 && n.x < self.width && n.y >= 0 && n.y < self.height {
+                        return Some(n);
+                    }
+                }
+                Topology::Wrapping => {
+                    return Some(Point::new(
+                        n.x.rem_euclid(self.width),
+                        n.y.rem_euclid(self.height),
+                    ));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(points: impl Iterator<Item = Point>) -> Vec<(i32, i32)> {
+        let mut points: Vec<_> = points.map(|p| (p.x, p.y)).collect();
+        points.sort_unstable();
+        points
+    }
+
+    #[test]
+    fn test_points() {
+        let grid = Grid::parse("#.#\n.##");
+        assert_eq!(grid.points().count(), 6);
+        assert_eq!(sorted(grid.points_with(b'#')), [(0, 0), (1, 1), (2, 0), (2, 1)]);
+        assert_eq!(sorted(grid.points_where(|&b| b == b'.')), [(0, 1), (1, 0)]);
+        assert_eq!(Grid::parse("").points().count(), 0);
+        assert_eq!(Grid::new(0, 2, 0u8).points().count(), 0);
+    }
+
+    #[test]
+    fn test_bounded_neighbors() {
+        let grid = Grid::new(3, 4, 0u8);
+        let corner = Point::new(0, 0);
+
+        assert_eq!(sorted(grid.orthogonal_neighbors(corner)), [(0, 1), (1, 0)]);
+        assert_eq!(sorted(grid.diagonal_neighbors(corner)), [(0, 1), (1, 0), (1, 1)]);
+        assert_eq!(grid.diagonal_neighbors(Point::new(1, 1)).count(), 8);
+        assert_eq!(grid.orthogonal_neighbors(Point::new(2, 3)).count(), 2);
+    }
+
+    #[test]
+    fn test_wrapping_neighbors() {
+        let grid = Grid::new(3, 4, 0u8);
+        let corner = Point::new(0, 0);
+
+        let orthogonal = grid.neighbors(corner, &ORTHOGONAL, Topology::Wrapping);
+        assert_eq!(sorted(orthogonal), [(0, 1), (0, 3), (1, 0), (2, 0)]);
+        let diagonal = grid.neighbors(corner, &DIAGONAL, Topology::Wrapping);
+        assert_eq!(
+            sorted(diagonal),
+            [(0, 1), (0, 3), (1, 0), (1, 1), (1, 3), (2, 0), (2, 1), (2, 3)]
+        );
+        let corner = Point::new(2, 3);
+        let orthogonal = grid.neighbors(corner, &ORTHOGONAL, Topology::Wrapping);
+        assert_eq!(sorted(orthogonal), [(0, 3), (1, 3), (2, 0), (2, 2)]);
+    }
+
+    #[test]
+    fn test_neighbor_values() {
+        let grid = Grid::parse("ab\ncd");
+        let values: Vec<_> = grid.neighbor_values(Point::new(0, 0), &ORTHOGONAL, b'.').collect();
+        assert_eq!(values, b".c.b");
+    }
+
+    #[test]
+    fn test_padded() {
+        let grid = Grid::parse("ab\ncd\nef");
+        let padded = grid.padded(2, b'.');
+        assert_eq!((padded.width, padded.height), (6, 7));
+        assert_eq!(padded, Grid::parse("......\n......\n..ab..\n..cd..\n..ef..\n......\n......"));
+        assert_eq!(grid.padded(0, b'.'), grid);
+    }
+}
@@ -0,0 +1,161 @@
+//! Dense 3-dimensional grid of voxels indexed by [`Point3`].
+//!
+//! [`parse`] reads layered text, where each layer is a block of lines with the same shape as the
+//! input to [`Grid::parse`] and layers are separated by blank lines. The first layer has `z = 0`.
+//! Empty input gives an empty grid.
+//!
+//! [`parse`]: Grid3::parse
+use crate::majcn::input::*;
+use crate::majcn::point3::*;
+use crate::maneatingape::grid::*;
+use std::ops::{Index, IndexMut};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid3<T> {
+    pub width: i32,
+    pub height: i32,
+    pub depth: i32,
+    pub bytes: Vec<T>,
+}
+
+impl Grid3<u8> {
+    pub fn parse(input: &str) -> Self {
+        let layers: Vec<_> = Input::new(input).iter().map(Section::grid).collect();
+
+        let Some(&Grid { width, height, .. }) = layers.first() else {
+            return Grid3 { width: 0, height: 0, depth: 0, bytes: Vec::new() };
+        };
+        assert!(
+            layers.iter().all(|layer| layer.width == width && layer.height == height),
+            "All layers must have the same size"
+        );
+
+        let depth = layers.len() as i32;
+        let bytes = layers.into_iter().flat_map(|layer| layer.bytes).collect();
+
+        Grid3 { width, height, depth, bytes }
+    }
+}
+
+impl<T: Copy + PartialEq> Grid3<T> {
+    #[inline]
+    pub fn find(&self, needle: T) -> Option<Point3> {
+        self.bytes.iter().position(|&h| h == needle).map(|index| self.point(index))
+    }
+}
+
+impl<T: Copy> Grid3<T> {
+    pub fn new(width: i32, height: i32, depth: i32, value: T) -> Grid3<T> {
+        Grid3 { width, height, depth, bytes: vec![value; (width * height * depth) as usize] }
+    }
+
+    /// The horizontal slice at depth `z`.
+    pub fn layer(&self, z: i32) -> Grid<T> {
+        let size = (self.width * self.height) as usize;
+        let start = z as usize * size;
+        Grid {
+            width: self.width,
+            height: self.height,
+            bytes: self.bytes[start..start + size].to_vec(),
+        }
+    }
+}
+
+impl<T> Grid3<T> {
+    #[inline]
+    pub fn same_size_with<U: Copy>(&self, value: U) -> Grid3<U> {
+        Grid3 {
+            width: self.width,
+            height: self.height,
+            depth: self.depth,
+            bytes: vec![value; (self.width * self.height * self.depth) as usize],
+        }
+    }
+
+    #[inline]
+    pub fn contains(&self, point: Point3) -> bool {
+        point.x >= 0
+            && point.x < self.width
+            && point.y >= 0
+            && point.y < self.height
+            && point.z >= 0
+            && point.z < self.depth
+    }
+
+    #[inline]
+    pub fn points(&self) -> impl Iterator<Item = Point3> + use<T> {
+        let (width, height) = (self.width, self.height);
+        (0..self.bytes.len() as i32)
+            .map(move |i| Point3::new(i % width, (i / width) % height, i / (width * height)))
+    }
+
+    /// Neighbors in `stencil` that are inside the grid.
+    #[inline]
+    pub fn neighbors<'a>(
+        &self,
+        point: Point3,
+        stencil: &'a [Point3],
+    ) -> impl Iterator<Item = Point3> + use<'a, T> {
+        let (width, height, depth) = (self.width, self.height, self.depth);
+        stencil.iter().map(move |&d| point + d).filter(move |n| {
+            n.x >= 0 && n.x < width && n.y >= 0 && n.y < height && n.z >= 0 && n.z < depth
+        })
+    }
+
+    #[inline]
+    fn point(&self, index: usize) -> Point3 {
+        let index = index as i32;
+        Point3::new(
+            index % self.width,
+            (index / self.width) % self.height,
+            index / (self.width * self.height),
+        )
+    }
+}
+
+impl<T> Index<Point3> for Grid3<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: Point3) -> &Self::Output {
+        &self.bytes[(self.width * (self.height * index.z + index.y) + index.x) as usize]
+    }
+}
+
+impl<T> IndexMut<Point3> for Grid3<T> {
+    #[inline]
+    fn index_mut(&mut self, index: Point3) -> &mut Self::Output {
+        &mut self.bytes[(self.width * (self.height * index.z + index.y) + index.x) as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid3::parse("ab\ncd\nef\n\ngh\nij\nkl\n");
+        assert_eq!((grid.width, grid.height, grid.depth), (2, 3, 2));
+        assert_eq!(grid[Point3::new(1, 2, 0)], b'f');
+        assert_eq!(grid[Point3::new(0, 1, 1)], b'i');
+        assert_eq!(grid.find(b'l'), Some(Point3::new(1, 2, 1)));
+        assert_eq!(grid.layer(1), Grid::parse("gh\nij\nkl"));
+        assert_eq!(grid.points().count(), 12);
+        assert_eq!(grid.neighbors(ORIGIN_3D, &DIAGONAL_3D).count(), 7);
+    }
+
+    #[test]
+    fn test_parse_empty() {
+        let grid = Grid3::parse("");
+        assert_eq!((grid.width, grid.height, grid.depth), (0, 0, 0));
+        assert_eq!(grid.points().count(), 0);
+        assert_eq!(Grid3::parse("\n\n"), grid);
+    }
+
+    #[test]
+    #[should_panic(expected = "All layers must have the same size")]
+    fn test_parse_different_sizes() {
+        Grid3::parse("ab\ncd\n\nab\n");
+    }
+}
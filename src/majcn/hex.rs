@@ -0,0 +1,297 @@
+//! Hexagonal grid coordinates.
+//!
+//! A [`Hex`] stores [axial coordinates](https://www.redblobgames.com/grids/hexagons/#coordinates-axial)
+//! `q` and `r`. The third cube coordinate is implied by `q + r + s = 0` and available through
+//! [`s`] and [`cube`]. The six neighbor offsets in [`HEX_DIRECTIONS`] are ordered counter
+//! clockwise.
+//!
+//! Puzzles use either flat topped hexes, with directions `n`, `ne`, `se`, `s`, `sw` and `nw`,
+//! or pointy topped hexes with `e`, `se`, `sw`, `w`, `nw` and `ne`. The diagonal directions
+//! mean different offsets in the two layouts, so each has its own parse function:
+//!
+//! ```
+//!   # use advent_of_code::majcn::hex::*;
+//!
+//!   let path = Hex::parse_flat_path("ne,ne,s,s").unwrap();
+//!   let end = path.into_iter().fold(HEX_ORIGIN, |acc, d| acc + d);
+//!   assert_eq!(end.distance(HEX_ORIGIN), 2);
+//!
+//!   let path = Hex::parse_pointy_path("nwwswee").unwrap();
+//!   assert_eq!(path.into_iter().fold(HEX_ORIGIN, |acc, d| acc + d), HEX_ORIGIN);
+//! ```
+//!
+//! [`s`]: Hex::s
+//! [`cube`]: Hex::cube
+use crate::majcn::direction::ParseDirectionError;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+pub const HEX_ORIGIN: Hex = Hex::new(0, 0);
+pub const HEX_DIRECTIONS: [Hex; 6] = [
+    Hex::new(1, 0),
+    Hex::new(1, -1),
+    Hex::new(0, -1),
+    Hex::new(-1, 0),
+    Hex::new(-1, 1),
+    Hex::new(0, 1),
+];
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Hex {
+    pub q: i32,
+    pub r: i32,
+}
+
+impl Hex {
+    #[inline]
+    #[must_use]
+    pub const fn new(q: i32, r: i32) -> Self {
+        Hex { q, r }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn from_cube(q: i32, r: i32, s: i32) -> Self {
+        assert_eq!(q + r + s, 0, "Cube coordinates must sum to zero");
+        Hex::new(q, r)
+    }
+
+    #[inline]
+    pub const fn s(self) -> i32 {
+        -self.q - self.r
+    }
+
+    #[inline]
+    pub const fn cube(self) -> [i32; 3] {
+        [self.q, self.r, self.s()]
+    }
+
+    #[inline]
+    pub fn neighbors(self) -> [Hex; 6] {
+        HEX_DIRECTIONS.map(|d| self + d)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn distance(self, other: Self) -> i32 {
+        let d = self - other;
+        (d.q.abs() + d.r.abs() + d.s().abs()) / 2
+    }
+
+    /// Rotates 60 degrees clockwise around the origin.
+    #[inline]
+    #[must_use]
+    pub fn clockwise(self) -> Self {
+        Hex::new(-self.r, -self.s())
+    }
+
+    /// Rotates 60 degrees counter clockwise around the origin.
+    #[inline]
+    #[must_use]
+    pub fn counter_clockwise(self) -> Self {
+        Hex::new(-self.s(), -self.q)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn clockwise_around(self, center: Self) -> Self {
+        (self - center).clockwise() + center
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn counter_clockwise_around(self, center: Self) -> Self {
+        (self - center).counter_clockwise() + center
+    }
+
+    /// All hexes at exactly `radius` steps from `self`, walking counter clockwise.
+    pub fn ring(self, radius: i32) -> impl Iterator<Item = Hex> {
+        let center = std::iter::once(self).filter(move |_| radius == 0);
+        let sides = (0..6).flat_map(move |i| {
+            let corner = self + HEX_DIRECTIONS[(i + 4) % 6] * radius;
+            (0..radius).map(move |j| corner + HEX_DIRECTIONS[i] * j)
+        });
+        center.chain(sides)
+    }
+
+    /// All hexes within `radius` steps of `self`, starting at the center and moving outward
+    /// one ring at a time.
+    pub fn spiral(self, radius: i32) -> impl Iterator<Item = Hex> {
+        (0..=radius).flat_map(move |r| self.ring(r))
+    }
+
+    /// Parses a single direction of a flat topped layout.
+    pub fn parse_flat(s: &str) -> Result<Hex, ParseDirectionError> {
+        match s.trim() {
+            "n" | "N" => Ok(Hex::new(0, -1)),
+            "ne" | "NE" => Ok(Hex::new(1, -1)),
+            "se" | "SE" => Ok(Hex::new(1, 0)),
+            "s" | "S" => Ok(Hex::new(0, 1)),
+            "sw" | "SW" => Ok(Hex::new(-1, 1)),
+            "nw" | "NW" => Ok(Hex::new(-1, 0)),
+            _ => Err(ParseDirectionError),
+        }
+    }
+
+    /// Parses a single direction of a pointy topped layout.
+    pub fn parse_pointy(s: &str) -> Result<Hex, ParseDirectionError> {
+        match s.trim() {
+            "e" | "E" => Ok(Hex::new(1, 0)),
+            "ne" | "NE" => Ok(Hex::new(1, -1)),
+            "nw" | "NW" => Ok(Hex::new(0, -1)),
+            "w" | "W" => Ok(Hex::new(-1, 0)),
+            "sw" | "SW" => Ok(Hex::new(-1, 1)),
+            "se" | "SE" => Ok(Hex::new(0, 1)),
+            _ => Err(ParseDirectionError),
+        }
+    }
+
+    /// Parses comma or whitespace separated flat topped directions such as `ne,se,n`.
+    pub fn parse_flat_path(s: &str) -> Result<Vec<Hex>, ParseDirectionError> {
+        s.split([',', ' ', '\n']).filter(|d| !d.is_empty()).map(Hex::parse_flat).collect()
+    }
+
+    /// Parses pointy topped directions, which may be written without separators as in `esenee`.
+    pub fn parse_pointy_path(s: &str) -> Result<Vec<Hex>, ParseDirectionError> {
+        let bytes = s.as_bytes();
+        let mut result = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let len = match bytes[i] {
+                b',' | b' ' | b'\n' | b'\r' => {
+                    i += 1;
+                    continue;
+                }
+                b'n' | b's' | b'N' | b'S' => 2,
+                _ => 1,
+            };
+            let direction = s.get(i..i + len).ok_or(ParseDirectionError)?;
+            result.push(Hex::parse_pointy(direction)?);
+            i += len;
+        }
+
+        Ok(result)
+    }
+}
+
+impl Hash for Hex {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u32(self.q as u32);
+        state.write_u32(self.r as u32);
+    }
+}
+
+impl Add for Hex {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Hex::new(self.q + rhs.q, self.r + rhs.r)
+    }
+}
+
+impl AddAssign for Hex {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        self.q += rhs.q;
+        self.r += rhs.r;
+    }
+}
+
+impl Mul<i32> for Hex {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: i32) -> Self {
+        Hex::new(self.q * rhs, self.r * rhs)
+    }
+}
+
+impl Neg for Hex {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Hex::new(-self.q, -self.r)
+    }
+}
+
+impl Sub for Hex {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Hex::new(self.q - rhs.q, self.r - rhs.r)
+    }
+}
+
+impl SubAssign for Hex {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        self.q -= rhs.q;
+        self.r -= rhs.r;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn walk(path: Vec<Hex>) -> Hex {
+        path.into_iter().fold(HEX_ORIGIN, |acc, d| acc + d)
+    }
+
+    #[test]
+    fn test_ring_and_spiral() {
+        let center = Hex::new(2, -5);
+        assert_eq!(center.ring(0).collect::<Vec<_>>(), [center]);
+
+        for radius in 1..6 {
+            let ring: HashSet<_> = center.ring(radius).collect();
+            assert_eq!(ring.len(), 6 * radius as usize);
+            assert!(ring.iter().all(|&hex| hex.distance(center) == radius));
+        }
+        for radius in 0..6 {
+            let spiral: HashSet<_> = center.spiral(radius).collect();
+            assert_eq!(spiral.len(), (3 * radius * (radius + 1) + 1) as usize);
+        }
+        assert_eq!(HashSet::from(center.neighbors()), center.ring(1).collect());
+    }
+
+    #[test]
+    fn test_rotation() {
+        let center = Hex::new(-1, 3);
+        let hex = Hex::new(4, -2);
+        assert_eq!((0..6).fold(hex, |h, _| h.clockwise_around(center)), hex);
+        assert_eq!((0..6).fold(hex, |h, _| h.counter_clockwise_around(center)), hex);
+        assert_eq!(hex.clockwise().counter_clockwise(), hex);
+        assert_eq!(hex.clockwise_around(center).distance(center), hex.distance(center));
+        assert_eq!(HEX_DIRECTIONS[1].clockwise(), HEX_DIRECTIONS[0]);
+        assert_eq!(Hex::from_cube(1, 2, -3).cube(), [1, 2, -3]);
+    }
+
+    #[test]
+    fn test_parse() {
+        let flat = ["n", "ne", "se", "s", "sw", "nw"].map(|d| Hex::parse_flat(d).unwrap());
+        let pointy = ["e", "ne", "nw", "w", "sw", "se"].map(|d| Hex::parse_pointy(d).unwrap());
+        assert_eq!(HashSet::from(flat), HashSet::from(HEX_DIRECTIONS));
+        assert_eq!(HashSet::from(pointy), HashSet::from(HEX_DIRECTIONS));
+
+        // The same moves written in both layouts end at the same coordinates.
+        let flat = Hex::parse_flat_path("se,se,n,nw,s,sw,sw,ne").unwrap();
+        let pointy = Hex::parse_pointy_path("eenwwseswswne").unwrap();
+        assert_eq!(flat, pointy);
+        assert_eq!(walk(flat), Hex::new(0, 1));
+
+        assert_eq!(walk(Hex::parse_flat_path("ne,ne,s,s").unwrap()).distance(HEX_ORIGIN), 2);
+        assert_eq!(walk(Hex::parse_pointy_path("nwwswee").unwrap()), HEX_ORIGIN);
+        assert_eq!(Hex::parse_pointy_path("se, w\nE").unwrap().len(), 3);
+
+        assert_eq!(Hex::parse_flat("e"), Err(ParseDirectionError));
+        assert_eq!(Hex::parse_pointy_path("ex"), Err(ParseDirectionError));
+        assert_eq!(Hex::parse_pointy_path("es"), Err(ParseDirectionError));
+    }
+}
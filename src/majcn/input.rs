@@ -0,0 +1,202 @@
+//! Splits puzzle input into sections separated by blank lines.
+//!
+//! Line endings may be `\n` or `\r\n`, runs of blank lines count as a single separator and
+//! leading or trailing blank lines are ignored. Lines with only spaces or tabs count as blank,
+//! since they are invisible in the puzzle text. Each [`Section`] borrows from the original input
+//! and can be read as raw lines, as a [`Grid`] or as numbers:
+//!
+//! ```
+//!   # use advent_of_code::majcn::input::*;
+//!
+//!   let input = Input::new("3-5\r\n10-14\r\n\r\n1\r\n5\r\n");
+//!   let ranges: Vec<u64> = input.section(0).unwrap().iter_unsigned().collect();
+//!   let ids: Vec<u64> = input.section(1).unwrap().iter_unsigned().collect();
+//!
+//!   assert_eq!(ranges, [3, 5, 10, 14]);
+//!   assert_eq!(ids, [1, 5]);
+//!   assert!(input.section(2).is_err());
+//! ```
+use crate::maneatingape::grid::*;
+use crate::maneatingape::parse::*;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::Lines;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputError {
+    MissingSection { index: usize, count: usize },
+}
+
+impl Error for InputError {}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::MissingSection { index, count } => {
+                write!(f, "missing section {index}, input only has {count} section(s)")
+            }
+        }
+    }
+}
+
+pub struct Input<'a> {
+    sections: Vec<Section<'a>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Section<'a> {
+    text: &'a str,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(input: &'a str) -> Self {
+        let mut sections = vec![];
+        let mut start = None;
+        let mut end = 0;
+        let mut offset = 0;
+
+        for line in input.split_inclusive('\n') {
+            let content = line.trim_end_matches(['\n', '\r']);
+            if content.trim_ascii().is_empty() {
+                if let Some(start) = start.take() {
+                    sections.push(Section { text: &input[start..end] });
+                }
+            } else {
+                start.get_or_insert(offset);
+                end = offset + content.len();
+            }
+            offset += line.len();
+        }
+
+        if let Some(start) = start {
+            sections.push(Section { text: &input[start..end] });
+        }
+
+        Input { sections }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.sections.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.sections.is_empty()
+    }
+
+    #[inline]
+    pub fn section(&self, index: usize) -> Result<Section<'a>, InputError> {
+        self.sections
+            .get(index)
+            .copied()
+            .ok_or(InputError::MissingSection { index, count: self.sections.len() })
+    }
+
+    /// Returns the last section together with all sections before it, for inputs that start
+    /// with a variable number of blocks of the same kind.
+    #[inline]
+    pub fn split_last(&self) -> Result<(Section<'a>, &[Section<'a>]), InputError> {
+        self.sections
+            .split_last()
+            .map(|(last, rest)| (*last, rest))
+            .ok_or(InputError::MissingSection { index: 0, count: 0 })
+    }
+
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = Section<'a>> + '_ {
+        self.sections.iter().copied()
+    }
+}
+
+impl<'a> Section<'a> {
+    #[inline]
+    pub fn as_str(self) -> &'a str {
+        self.text
+    }
+
+    #[inline]
+    pub fn lines(self) -> Lines<'a> {
+        self.text.lines()
+    }
+
+    #[inline]
+    pub fn grid(self) -> Grid<u8> {
+        Grid::parse(self.text)
+    }
+
+    #[inline]
+    pub fn unsigned<T: UnsignedDigits>(self) -> T {
+        self.text.unsigned()
+    }
+
+    #[inline]
+    pub fn signed<T: SignedDigits>(self) -> T {
+        self.text.signed()
+    }
+
+    #[inline]
+    pub fn iter_unsigned<T: UnsignedDigits>(self) -> ParseUnsigned<'a, T> {
+        self.text.iter_unsigned()
+    }
+
+    #[inline]
+    pub fn iter_signed<T: SignedDigits>(self) -> ParseSigned<'a, T> {
+        self.text.iter_signed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sections<'a>(input: &Input<'a>) -> Vec<&'a str> {
+        input.iter().map(Section::as_str).collect()
+    }
+
+    #[test]
+    fn test_sections() {
+        let input = Input::new("a\nb\n\nc\n");
+        assert_eq!(sections(&input), ["a\nb", "c"]);
+
+        let input = Input::new("a\r\nb\r\n\r\nc\r\n");
+        assert_eq!(sections(&input), ["a\r\nb", "c"]);
+        assert_eq!(input.section(0).unwrap().lines().collect::<Vec<_>>(), ["a", "b"]);
+
+        let input = Input::new("\n\na\n\n\n\nb\n\n\n");
+        assert_eq!(sections(&input), ["a", "b"]);
+
+        let input = Input::new("a\n  \t\nb\n \n");
+        assert_eq!(sections(&input), ["a", "b"]);
+
+        assert!(Input::new("").is_empty());
+        assert!(Input::new("\r\n\n").is_empty());
+    }
+
+    #[test]
+    fn test_missing_section() {
+        let input = Input::new("a\n\nb");
+        assert_eq!(input.len(), 2);
+        assert_eq!(input.section(2), Err(InputError::MissingSection { index: 2, count: 2 }));
+        assert_eq!(
+            input.section(2).unwrap_err().to_string(),
+            "missing section 2, input only has 2 section(s)"
+        );
+    }
+
+    #[test]
+    fn test_split_last() {
+        let input = Input::new("1\n\n2\n\n3 4\n");
+        let (last, rest) = input.split_last().unwrap();
+        assert_eq!(last.iter_unsigned::<u32>().collect::<Vec<_>>(), [3, 4]);
+        assert_eq!(rest.iter().map(|s| s.unsigned::<u32>()).collect::<Vec<_>>(), [1, 2]);
+
+        let input = Input::new("#.\n.#");
+        let (last, rest) = input.split_last().unwrap();
+        assert_eq!(last.grid().width, 2);
+        assert!(rest.is_empty());
+
+        let error = Input::new("\n").split_last().unwrap_err();
+        assert_eq!(error, InputError::MissingSection { index: 0, count: 0 });
+    }
+}
@@ -0,0 +1,293 @@
+//! Set of integers stored as sorted, disjoint half-open ranges.
+//!
+//! Inserting a range merges it with any ranges that it overlaps or touches, so `0..3` and
+//! `3..5` become `0..5`, and removing a range splits the ranges around it. Puzzles often give
+//! inclusive ranges such as `3-5`, which [`inclusive`] and [`insert_inclusive`] convert to
+//! half-open form. The upper bound of an inclusive range must be less than `T::MAX`.
+//!
+//! [`inclusive`]: IntervalSet::inclusive
+//! [`insert_inclusive`]: IntervalSet::insert_inclusive
+use crate::maneatingape::integer::*;
+use std::ops::{Range, RangeInclusive};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: Integer<T> + Ord> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet { ranges: vec![] }
+    }
+
+    /// Set of all values in `start..end`.
+    pub fn half_open(start: T, end: T) -> Self {
+        let mut set = Self::new();
+        set.insert(start..end);
+        set
+    }
+
+    /// Set of all values in `start..=end`.
+    pub fn inclusive(start: T, end: T) -> Self {
+        let mut set = Self::new();
+        set.insert_inclusive(start..=end);
+        set
+    }
+
+    /// Number of values in the set.
+    #[inline]
+    pub fn len(&self) -> T {
+        self.ranges.iter().fold(T::ZERO, |acc, r| acc + (r.end - r.start))
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Number of disjoint ranges.
+    #[inline]
+    pub fn range_count(&self) -> usize {
+        self.ranges.len()
+    }
+
+    #[inline]
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.ranges.partition_point(|r| r.start <= value);
+        index > 0 && value < self.ranges[index - 1].end
+    }
+
+    /// The disjoint ranges in increasing order.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.ranges.iter().cloned()
+    }
+
+    /// Every value in the set in increasing order.
+    #[inline]
+    pub fn values(&self) -> impl Iterator<Item = T> + '_ {
+        self.ranges.iter().flat_map(|r| {
+            let end = r.end;
+            std::iter::successors(Some(r.start), |&v| Some(v + T::ONE))
+                .take_while(move |&v| v < end)
+        })
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        let Range { mut start, mut end } = range;
+        if start >= end {
+            return;
+        }
+
+        let from = self.ranges.partition_point(|r| r.end < start);
+        let to = self.ranges.partition_point(|r| r.start <= end);
+        if from < to {
+            start = start.min(self.ranges[from].start);
+            end = end.max(self.ranges[to - 1].end);
+        }
+
+        self.ranges.splice(from..to, [start..end]);
+    }
+
+    #[inline]
+    pub fn insert_inclusive(&mut self, range: RangeInclusive<T>) {
+        self.insert(*range.start()..*range.end() + T::ONE);
+    }
+
+    pub fn remove(&mut self, range: Range<T>) {
+        let Range { start, end } = range;
+        if start >= end {
+            return;
+        }
+
+        let from = self.ranges.partition_point(|r| r.end <= start);
+        let to = self.ranges.partition_point(|r| r.start < end);
+        if from == to {
+            return;
+        }
+
+        let first = self.ranges[from].start;
+        let last = self.ranges[to - 1].end;
+        let left = (first < start).then_some(first..start);
+        let right = (end < last).then_some(end..last);
+
+        self.ranges.splice(from..to, left.into_iter().chain(right));
+    }
+
+    #[inline]
+    pub fn remove_inclusive(&mut self, range: RangeInclusive<T>) {
+        self.remove(*range.start()..*range.end() + T::ONE);
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        result.extend(other.iter());
+        result
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let (mut i, mut j) = (0, 0);
+
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let start = a.start.max(b.start);
+            let end = a.end.min(b.end);
+            if start < end {
+                ranges.push(start..end);
+            }
+
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        IntervalSet { ranges }
+    }
+
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        other.iter().for_each(|r| result.remove(r));
+        result
+    }
+}
+
+impl<T: Integer<T> + Ord> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Integer<T> + Ord> Extend<Range<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Range<T>>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|r| self.insert(r));
+    }
+}
+
+impl<T: Integer<T> + Ord> Extend<RangeInclusive<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|r| self.insert_inclusive(r));
+    }
+}
+
+impl<T: Integer<T> + Ord> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Integer<T> + Ord> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::majcn::testing::Rng;
+
+    const SIZE: u32 = 64;
+
+    fn range(rng: &mut Rng) -> Range<u32> {
+        let a = rng.below(SIZE as u64 + 1) as u32;
+        let b = rng.below(SIZE as u64 + 1) as u32;
+        a.min(b)..a.max(b)
+    }
+
+    fn mask(range: Range<u32>) -> u64 {
+        range.fold(0, |acc, v| acc | 1 << v)
+    }
+
+    fn random_set(rng: &mut Rng) -> (IntervalSet<u32>, u64) {
+        let mut set = IntervalSet::new();
+        let mut model = 0;
+
+        for _ in 0..rng.below(8) {
+            let range = range(rng);
+            model |= mask(range.clone());
+            set.insert(range);
+        }
+
+        (set, model)
+    }
+
+    fn check(set: &IntervalSet<u32>, model: u64) {
+        assert_eq!(set.len(), model.count_ones());
+        assert_eq!(set.is_empty(), model == 0);
+        assert!((0..SIZE).all(|v| set.contains(v) == (model & 1 << v != 0)));
+        assert_eq!(set.values().fold(0, |acc, v| acc | 1 << v), model);
+
+        let ranges: Vec<_> = set.iter().collect();
+        assert!(ranges.iter().all(|r| r.start < r.end));
+        assert!(ranges.windows(2).all(|w| w[0].end < w[1].start));
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut rng = Rng::new();
+
+        for _ in 0..200 {
+            let mut set = IntervalSet::new();
+            let mut model = 0;
+
+            for _ in 0..20 {
+                let range = range(&mut rng);
+                match rng.below(4) {
+                    0 => {
+                        model |= mask(range.clone());
+                        set.insert(range);
+                    }
+                    1 => {
+                        model &= !mask(range.clone());
+                        set.remove(range);
+                    }
+                    2 if !range.is_empty() => {
+                        model |= mask(range.clone());
+                        set.insert_inclusive(range.start..=range.end - 1);
+                    }
+                    _ if !range.is_empty() => {
+                        model &= !mask(range.clone());
+                        set.remove_inclusive(range.start..=range.end - 1);
+                    }
+                    _ => (),
+                }
+                check(&set, model);
+            }
+        }
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let mut rng = Rng::new();
+
+        for _ in 0..500 {
+            let (a, model_a) = random_set(&mut rng);
+            let (b, model_b) = random_set(&mut rng);
+
+            check(&a.union(&b), model_a | model_b);
+            check(&a.intersection(&b), model_a & model_b);
+            check(&a.difference(&b), model_a & !model_b);
+        }
+    }
+
+    #[test]
+    fn test_constructors() {
+        assert_eq!(IntervalSet::inclusive(3, 5), IntervalSet::half_open(3, 6));
+        assert_eq!(IntervalSet::inclusive(3, 5).len(), 3);
+        assert!(IntervalSet::half_open(3, 3).is_empty());
+
+        let set: IntervalSet<i64> = [-5..=-1, 0..=2, 10..=12].into_iter().collect();
+        assert_eq!(set.iter().collect::<Vec<_>>(), [-5..3, 10..13]);
+        assert_eq!(set.range_count(), 2);
+    }
+}
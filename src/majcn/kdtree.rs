@@ -0,0 +1,327 @@
+//! [k-d tree](https://en.wikipedia.org/wiki/K-d_tree) over a fixed set of 2D or 3D points.
+//!
+//! The tree is stored implicitly in a single permutation of the point indices, where the median
+//! of each range is the node that splits it. All queries return indices into the slice that the
+//! tree was built from and measure distance as the squared Euclidean distance.
+//!
+//! [`closest_pairs`] lazily yields every pair of points in increasing distance, which is what
+//! [Kruskal's algorithm](https://en.wikipedia.org/wiki/Kruskal%27s_algorithm) needs, without
+//! first materializing all n² pairs. Each point keeps a buffer of its nearest neighbors that is
+//! refilled with twice as many neighbors whenever it runs out. Pairs at the same distance are
+//! ordered by their indices `(i, j)`, so the order is deterministic.
+//!
+//! [`closest_pairs`]: KdTree::closest_pairs
+use crate::majcn::point3::*;
+use crate::maneatingape::heap::*;
+use crate::maneatingape::point::*;
+use std::collections::BinaryHeap;
+
+pub trait KdPoint: Copy {
+    const DIMENSIONS: usize;
+
+    fn coordinate(&self, axis: usize) -> i64;
+
+    #[inline]
+    fn distance_squared(&self, other: &Self) -> i64 {
+        (0..Self::DIMENSIONS)
+            .map(|axis| {
+                let d = self.coordinate(axis) - other.coordinate(axis);
+                d * d
+            })
+            .sum()
+    }
+}
+
+impl KdPoint for Point {
+    const DIMENSIONS: usize = 2;
+
+    #[inline]
+    fn coordinate(&self, axis: usize) -> i64 {
+        if axis == 0 { self.x as i64 } else { self.y as i64 }
+    }
+}
+
+impl KdPoint for Point64 {
+    const DIMENSIONS: usize = 2;
+
+    #[inline]
+    fn coordinate(&self, axis: usize) -> i64 {
+        if axis == 0 { self.x } else { self.y }
+    }
+}
+
+impl KdPoint for Point3 {
+    const DIMENSIONS: usize = 3;
+
+    #[inline]
+    fn coordinate(&self, axis: usize) -> i64 {
+        match axis {
+            0 => self.x as i64,
+            1 => self.y as i64,
+            _ => self.z as i64,
+        }
+    }
+}
+
+pub struct KdTree<P> {
+    points: Vec<P>,
+    order: Vec<usize>,
+}
+
+impl<P: KdPoint> KdTree<P> {
+    pub fn new(points: &[P]) -> Self {
+        let mut order: Vec<_> = (0..points.len()).collect();
+        build(points, &mut order, 0);
+        KdTree { points: points.to_vec(), order }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    #[inline]
+    pub fn nearest(&self, target: P) -> Option<(i64, usize)> {
+        self.k_nearest(target, 1).into_iter().next()
+    }
+
+    /// The `k` points closest to `target` as `(distance, index)` in increasing order.
+    pub fn k_nearest(&self, target: P, k: usize) -> Vec<(i64, usize)> {
+        let mut best = BinaryHeap::with_capacity(k + 1);
+        if k > 0 {
+            self.search_nearest(0, self.order.len(), 0, target, k, &mut best);
+        }
+        best.into_sorted_vec()
+    }
+
+    /// Indices of all points with a squared distance to `target` of at most `radius_squared`.
+    pub fn within(&self, target: P, radius_squared: i64) -> Vec<usize> {
+        let mut result = vec![];
+        self.search_within(0, self.order.len(), 0, target, radius_squared, &mut result);
+        result
+    }
+
+    /// Every pair of points `(distance, i, j)` with `i < j`, in increasing order of distance.
+    pub fn closest_pairs(&self) -> ClosestPairs<'_, P> {
+        let n = self.len();
+        let mut pairs = ClosestPairs {
+            tree: self,
+            buffers: vec![vec![]; n],
+            positions: vec![0; n],
+            fetched: vec![0; n],
+            heap: MinHeap::with_capacity(n),
+        };
+        (0..n).for_each(|i| pairs.advance(i));
+        pairs
+    }
+
+    fn search_nearest(
+        &self,
+        lo: usize,
+        hi: usize,
+        depth: usize,
+        target: P,
+        k: usize,
+        best: &mut BinaryHeap<(i64, usize)>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+
+        let mid = (lo + hi) / 2;
+        let index = self.order[mid];
+        let candidate = (self.points[index].distance_squared(&target), index);
+        if best.len() < k {
+            best.push(candidate);
+        } else if candidate < *best.peek().unwrap() {
+            best.pop();
+            best.push(candidate);
+        }
+
+        let axis = depth % P::DIMENSIONS;
+        let delta = target.coordinate(axis) - self.points[index].coordinate(axis);
+        let (near, far) =
+            if delta < 0 { ((lo, mid), (mid + 1, hi)) } else { ((mid + 1, hi), (lo, mid)) };
+
+        self.search_nearest(near.0, near.1, depth + 1, target, k, best);
+        if best.len() < k || delta * delta <= best.peek().unwrap().0 {
+            self.search_nearest(far.0, far.1, depth + 1, target, k, best);
+        }
+    }
+
+    fn search_within(
+        &self,
+        lo: usize,
+        hi: usize,
+        depth: usize,
+        target: P,
+        radius_squared: i64,
+        result: &mut Vec<usize>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+
+        let mid = (lo + hi) / 2;
+        let index = self.order[mid];
+        if self.points[index].distance_squared(&target) <= radius_squared {
+            result.push(index);
+        }
+
+        let axis = depth % P::DIMENSIONS;
+        let delta = target.coordinate(axis) - self.points[index].coordinate(axis);
+        if delta <= 0 || delta * delta <= radius_squared {
+            self.search_within(lo, mid, depth + 1, target, radius_squared, result);
+        }
+        if delta >= 0 || delta * delta <= radius_squared {
+            self.search_within(mid + 1, hi, depth + 1, target, radius_squared, result);
+        }
+    }
+}
+
+fn build<P: KdPoint>(points: &[P], order: &mut [usize], depth: usize) {
+    if order.len() <= 1 {
+        return;
+    }
+
+    let mid = order.len() / 2;
+    let axis = depth % P::DIMENSIONS;
+    order.select_nth_unstable_by_key(mid, |&i| points[i].coordinate(axis));
+
+    let (left, right) = order.split_at_mut(mid);
+    build(points, left, depth + 1);
+    build(points, &mut right[1..], depth + 1);
+}
+
+pub struct ClosestPairs<'a, P> {
+    tree: &'a KdTree<P>,
+    buffers: Vec<Vec<(i64, usize)>>,
+    positions: Vec<usize>,
+    fetched: Vec<usize>,
+    heap: MinHeap<(i64, usize, usize), ()>,
+}
+
+impl<P: KdPoint> ClosestPairs<'_, P> {
+    /// Queues the next neighbor `j > i` of point `i`, fetching more neighbors if needed.
+    fn advance(&mut self, i: usize) {
+        let n = self.tree.len();
+
+        loop {
+            while let Some(&(distance, j)) = self.buffers[i].get(self.positions[i]) {
+                self.positions[i] += 1;
+                if j > i {
+                    self.heap.push((distance, i, j), ());
+                    return;
+                }
+            }
+
+            if self.fetched[i] >= n {
+                self.buffers[i] = vec![];
+                return;
+            }
+
+            let previous = self.fetched[i];
+            self.fetched[i] = (2 * previous).max(8).min(n);
+            self.buffers[i] = self.tree.k_nearest(self.tree.points[i], self.fetched[i]);
+            self.positions[i] = previous;
+        }
+    }
+}
+
+impl<P: KdPoint> Iterator for ClosestPairs<'_, P> {
+    type Item = (i64, usize, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let ((distance, i, j), ()) = self.heap.pop()?;
+        self.advance(i);
+        Some((distance, i, j))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::majcn::testing::Rng;
+
+    /// Small coordinates so that many points are duplicates or at the same distance.
+    fn random_points(rng: &mut Rng, n: usize) -> Vec<Point3> {
+        let mut coordinate = || rng.below(6) as i32 - 3;
+        (0..n).map(|_| Point3::new(coordinate(), coordinate(), coordinate())).collect()
+    }
+
+    fn brute_force(points: &[Point3], target: Point3) -> Vec<(i64, usize)> {
+        let mut all: Vec<_> = points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (KdPoint::distance_squared(p, &target), i))
+            .collect();
+        all.sort_unstable();
+        all
+    }
+
+    #[test]
+    fn test_k_nearest() {
+        let mut rng = Rng::new();
+
+        for n in [0, 1, 2, 7, 50] {
+            let points = random_points(&mut rng, n);
+            let tree = KdTree::new(&points);
+
+            for target in random_points(&mut rng, 20) {
+                let expected = brute_force(&points, target);
+                for k in [0, 1, 3, 8, n, n + 5] {
+                    assert_eq!(tree.k_nearest(target, k), expected[..k.min(n)]);
+                }
+                assert_eq!(tree.nearest(target), expected.first().copied());
+
+                let mut within = tree.within(target, 5);
+                within.sort_unstable();
+                let mut expected: Vec<_> =
+                    expected.iter().filter(|&&(d, _)| d <= 5).map(|&(_, i)| i).collect();
+                expected.sort_unstable();
+                assert_eq!(within, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_closest_pairs() {
+        let mut rng = Rng::new();
+
+        // 40 points need several refetches of each neighbor buffer, starting from 8.
+        for n in [0, 1, 2, 9, 40] {
+            let points = random_points(&mut rng, n);
+            let tree = KdTree::new(&points);
+
+            let mut expected = vec![];
+            for i in 0..n {
+                for j in i + 1..n {
+                    expected.push((KdPoint::distance_squared(&points[i], &points[j]), i, j));
+                }
+            }
+            expected.sort_unstable();
+
+            assert_eq!(tree.closest_pairs().collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let points = [Point::new(1, 1); 20];
+        let tree = KdTree::new(&points);
+
+        assert_eq!(tree.nearest(Point::new(0, 0)), Some((2, 0)));
+        assert_eq!(
+            tree.k_nearest(Point::new(1, 1), 20),
+            (0..20).map(|i| (0, i)).collect::<Vec<_>>()
+        );
+        assert_eq!(tree.closest_pairs().count(), 190);
+        assert!(tree.closest_pairs().all(|(d, i, j)| d == 0 && i < j));
+    }
+}
@@ -0,0 +1,601 @@
+//! Exact linear algebra for systems `A·x = b` with integer coefficients.
+//!
+//! [`LinearSystem`] reduces the augmented matrix to
+//! [reduced row echelon form](https://en.wikipedia.org/wiki/Row_echelon_form) over [`Rational`]
+//! numbers, so there is no floating point error. Every solution is then determined by the values
+//! of the free variables, which can be enumerated with [`for_each_integer_solution`].
+//! [`nullspace`] returns a basis of the homogeneous solutions scaled to integers.
+//!
+//! [`minimize`] solves small [integer linear programs](https://en.wikipedia.org/wiki/Integer_programming)
+//! with bounded non-negative variables by branch and bound over the free variables. Each pivot
+//! variable is bounded with interval arithmetic over the free variables that are not yet fixed,
+//! which prunes both infeasible branches and branches that cannot beat the best objective so far.
+//!
+//! [`for_each_integer_solution`]: LinearSystem::for_each_integer_solution
+//! [`nullspace`]: LinearSystem::nullspace
+use crate::maneatingape::math::*;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Div, Mul, Neg, Range, RangeInclusive, Sub};
+
+/// Fraction in lowest terms with a positive denominator.
+///
+/// Arithmetic is done in `i128` and panics if the reduced result does not fit in `i64`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i64,
+    den: i64,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { num: 0, den: 1 };
+    pub const ONE: Rational = Rational { num: 1, den: 1 };
+
+    /// Panics if `den` is zero.
+    #[inline]
+    pub fn new(num: i64, den: i64) -> Self {
+        Self::reduce(num as i128, den as i128)
+    }
+
+    #[inline]
+    pub fn numerator(self) -> i64 {
+        self.num
+    }
+
+    #[inline]
+    pub fn denominator(self) -> i64 {
+        self.den
+    }
+
+    #[inline]
+    pub fn is_zero(self) -> bool {
+        self.num == 0
+    }
+
+    #[inline]
+    pub fn is_integer(self) -> bool {
+        self.den == 1
+    }
+
+    #[inline]
+    pub fn to_integer(self) -> Option<i64> {
+        self.is_integer().then_some(self.num)
+    }
+
+    fn reduce(num: i128, den: i128) -> Self {
+        assert!(den != 0, "Denominator must not be zero");
+        let divisor = num.abs().gcd(den.abs()) * den.signum();
+        let num = i64::try_from(num / divisor).expect("Rational overflows i64");
+        let den = i64::try_from(den / divisor).expect("Rational overflows i64");
+        Rational { num, den }
+    }
+}
+
+impl From<i64> for Rational {
+    #[inline]
+    fn from(value: i64) -> Self {
+        Rational { num: value, den: 1 }
+    }
+}
+
+impl PartialOrd for Rational {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.num as i128 * other.den as i128).cmp(&(other.num as i128 * self.den as i128))
+    }
+}
+
+impl Add for Rational {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        let num = self.num as i128 * rhs.den as i128 + rhs.num as i128 * self.den as i128;
+        Self::reduce(num, self.den as i128 * rhs.den as i128)
+    }
+}
+
+impl Sub for Rational {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl Mul for Rational {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        Self::reduce(self.num as i128 * rhs.num as i128, self.den as i128 * rhs.den as i128)
+    }
+}
+
+impl Div for Rational {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, rhs: Self) -> Self {
+        Self::reduce(self.num as i128 * rhs.den as i128, self.den as i128 * rhs.num as i128)
+    }
+}
+
+impl Neg for Rational {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Rational { num: -self.num, den: self.den }
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+pub struct LinearSystem {
+    /// Reduced rows `[coefficients.., constant]`, one per pivot.
+    rows: Vec<Vec<Rational>>,
+    pivots: Vec<usize>,
+    free: Vec<usize>,
+    columns: usize,
+    consistent: bool,
+}
+
+impl LinearSystem {
+    /// Reduces `a·x = b`, where `a` has one row per equation.
+    pub fn new(a: &[Vec<i64>], b: &[i64]) -> Self {
+        let columns = a.first().map_or(0, Vec::len);
+        let mut rows: Vec<Vec<_>> = a
+            .iter()
+            .zip(b)
+            .map(|(row, &constant)| {
+                row.iter().chain([&constant]).map(|&v| Rational::from(v)).collect()
+            })
+            .collect();
+
+        let mut pivots = vec![];
+        let mut free = vec![];
+
+        for column in 0..columns {
+            let rank = pivots.len();
+            let Some(found) = (rank..rows.len()).find(|&r| !rows[r][column].is_zero()) else {
+                free.push(column);
+                continue;
+            };
+            rows.swap(rank, found);
+
+            let pivot = rows[rank][column];
+            rows[rank].iter_mut().for_each(|v| *v = *v / pivot);
+
+            let pivot_row = rows[rank].clone();
+            for (r, row) in rows.iter_mut().enumerate() {
+                let factor = row[column];
+                if r != rank && !factor.is_zero() {
+                    for (v, &p) in row.iter_mut().zip(&pivot_row).skip(column) {
+                        *v = *v - factor * p;
+                    }
+                }
+            }
+
+            pivots.push(column);
+        }
+
+        let consistent = rows[pivots.len()..].iter().all(|row| row[columns].is_zero());
+        rows.truncate(pivots.len());
+
+        LinearSystem { rows, pivots, free, columns, consistent }
+    }
+
+    #[inline]
+    pub fn is_consistent(&self) -> bool {
+        self.consistent
+    }
+
+    #[inline]
+    pub fn rank(&self) -> usize {
+        self.pivots.len()
+    }
+
+    #[inline]
+    pub fn pivot_variables(&self) -> &[usize] {
+        &self.pivots
+    }
+
+    #[inline]
+    pub fn free_variables(&self) -> &[usize] {
+        &self.free
+    }
+
+    /// Solution for the given values of the free variables, in the order of
+    /// [`free_variables`](Self::free_variables). `None` if the system is inconsistent.
+    pub fn solution(&self, free_values: &[Rational]) -> Option<Vec<Rational>> {
+        if !self.consistent {
+            return None;
+        }
+
+        let mut x = vec![Rational::ZERO; self.columns];
+        for (&f, &value) in self.free.iter().zip(free_values) {
+            x[f] = value;
+        }
+        for (row, &p) in self.rows.iter().zip(&self.pivots) {
+            x[p] = self.free.iter().fold(row[self.columns], |acc, &f| acc - row[f] * x[f]);
+        }
+
+        Some(x)
+    }
+
+    /// Basis of all solutions to `a·x = 0`, one integer vector per free variable.
+    pub fn nullspace(&self) -> Vec<Vec<i64>> {
+        self.free
+            .iter()
+            .map(|&f| {
+                let mut v = vec![Rational::ZERO; self.columns];
+                v[f] = Rational::ONE;
+                for (row, &p) in self.rows.iter().zip(&self.pivots) {
+                    v[p] = -row[f];
+                }
+
+                let scale = v.iter().fold(1, |acc, r| acc.lcm(r.denominator()));
+                let v: Vec<_> =
+                    v.iter().map(|r| r.numerator() * (scale / r.denominator())).collect();
+                let divisor = v.iter().fold(0, |acc: i64, n| acc.gcd(n.abs()));
+                v.into_iter().map(|n| n / divisor).collect()
+            })
+            .collect()
+    }
+
+    /// Calls `callback` with every solution where the free variables take integer values from
+    /// `ranges` and all pivot variables turn out to be integers as well.
+    pub fn for_each_integer_solution(
+        &self,
+        ranges: &[RangeInclusive<i64>],
+        mut callback: impl FnMut(&[i64]),
+    ) {
+        if !self.consistent {
+            return;
+        }
+
+        let form = IntegerForm::new(self, &vec![Rational::ZERO; self.columns]);
+        let mut x = vec![0; self.columns];
+        let mut remainders: Vec<_> = form.rows.iter().map(|row| row.constant).collect();
+        form.enumerate(0, ranges, &mut x, &mut remainders, &mut callback);
+    }
+}
+
+/// Minimum of `objective·x` over integer `x` with `a·x = b` and `0 <= x[i] <= upper[i]`,
+/// along with a vector that reaches it.
+pub fn minimize(
+    objective: &[i64],
+    a: &[Vec<i64>],
+    b: &[i64],
+    upper: &[i64],
+) -> Option<(i64, Vec<i64>)> {
+    let system = LinearSystem::new(a, b);
+    if !system.consistent {
+        return None;
+    }
+
+    let objective: Vec<_> = objective.iter().map(|&c| Rational::from(c)).collect();
+    let form = IntegerForm::new(&system, &objective);
+
+    let mut search = BranchAndBound {
+        form: &form,
+        upper,
+        x: vec![0; system.columns],
+        remainders: form.rows.iter().map(|row| row.constant).collect(),
+        best: None,
+    };
+    search.branch(0, form.objective.constant);
+
+    search.best.map(|(value, x)| (value / form.objective.scale, x))
+}
+
+/// Row `scale·x[pivot] = constant - Σ coefficients[i]·x[free[i]]` with integer values only.
+struct IntegerRow {
+    pivot: usize,
+    scale: i64,
+    constant: i64,
+    coefficients: Vec<i64>,
+}
+
+struct IntegerForm<'a> {
+    free: &'a [usize],
+    rows: Vec<IntegerRow>,
+    /// The objective as `scale·objective = constant - Σ coefficients[i]·x[free[i]]`.
+    objective: IntegerRow,
+}
+
+impl<'a> IntegerForm<'a> {
+    fn new(system: &'a LinearSystem, objective: &[Rational]) -> Self {
+        let scaled = |pivot: usize, constant: Rational, coefficients: Vec<Rational>| {
+            let scale =
+                coefficients.iter().fold(constant.denominator(), |acc, r| acc.lcm(r.denominator()));
+            let to_integer = |r: Rational| r.numerator() * (scale / r.denominator());
+            IntegerRow {
+                pivot,
+                scale,
+                constant: to_integer(constant),
+                coefficients: coefficients.into_iter().map(to_integer).collect(),
+            }
+        };
+
+        let rows: Vec<_> = system
+            .rows
+            .iter()
+            .zip(&system.pivots)
+            .map(|(row, &p)| {
+                let coefficients = system.free.iter().map(|&f| row[f]).collect();
+                scaled(p, row[system.columns], coefficients)
+            })
+            .collect();
+
+        // Substitute every pivot variable into the objective.
+        let constant = system
+            .rows
+            .iter()
+            .zip(&system.pivots)
+            .fold(Rational::ZERO, |acc, (row, &p)| acc + objective[p] * row[system.columns]);
+        let coefficients = system
+            .free
+            .iter()
+            .map(|&f| {
+                system
+                    .rows
+                    .iter()
+                    .zip(&system.pivots)
+                    .fold(-objective[f], |acc, (row, &p)| acc + objective[p] * row[f])
+            })
+            .collect();
+        let objective = scaled(usize::MAX, constant, coefficients);
+
+        IntegerForm { free: &system.free, rows, objective }
+    }
+
+    fn enumerate(
+        &self,
+        depth: usize,
+        ranges: &[RangeInclusive<i64>],
+        x: &mut [i64],
+        remainders: &mut [i64],
+        callback: &mut impl FnMut(&[i64]),
+    ) {
+        if depth == self.free.len() {
+            if self.solve_pivots(x, remainders) {
+                callback(x);
+            }
+            return;
+        }
+
+        for value in ranges[depth].clone() {
+            x[self.free[depth]] = value;
+            self.fix(depth, value, remainders);
+            self.enumerate(depth + 1, ranges, x, remainders, callback);
+            self.fix(depth, -value, remainders);
+        }
+    }
+
+    #[inline]
+    fn fix(&self, depth: usize, value: i64, remainders: &mut [i64]) {
+        for (remainder, row) in remainders.iter_mut().zip(&self.rows) {
+            *remainder -= row.coefficients[depth] * value;
+        }
+    }
+
+    #[inline]
+    fn solve_pivots(&self, x: &mut [i64], remainders: &[i64]) -> bool {
+        self.rows.iter().zip(remainders).all(|(row, &remainder)| {
+            x[row.pivot] = remainder / row.scale;
+            remainder % row.scale == 0
+        })
+    }
+}
+
+struct BranchAndBound<'a> {
+    form: &'a IntegerForm<'a>,
+    upper: &'a [i64],
+    x: Vec<i64>,
+    remainders: Vec<i64>,
+    best: Option<(i64, Vec<i64>)>,
+}
+
+impl BranchAndBound<'_> {
+    /// Range of `Σ -coefficients[i]·x[free[i]]` over the free variables in `open`.
+    fn spread(&self, coefficients: &[i64], open: Range<usize>) -> (i64, i64) {
+        open.fold((0, 0), |(lo, hi), i| {
+            let term = -coefficients[i] * self.upper[self.form.free[i]];
+            (lo + term.min(0), hi + term.max(0))
+        })
+    }
+
+    /// `objective` is the scaled objective with the free variables before `depth` fixed.
+    fn branch(&mut self, depth: usize, objective: i64) {
+        let form = self.form;
+
+        if depth == form.free.len() {
+            let in_bounds = form.rows.iter().zip(&self.remainders).all(|(row, &remainder)| {
+                (0..=row.scale * self.upper[row.pivot]).contains(&remainder)
+            });
+            if in_bounds && form.solve_pivots(&mut self.x, &self.remainders) {
+                self.best = Some((objective, self.x.clone()));
+            }
+            return;
+        }
+
+        // Narrow the range of the next free variable so that every pivot variable can still
+        // reach its bounds and the objective can still beat the best solution.
+        let open = depth + 1..form.free.len();
+        let mut lo = 0;
+        let mut hi = self.upper[form.free[depth]];
+
+        for (row, &remainder) in form.rows.iter().zip(&self.remainders) {
+            let c = row.coefficients[depth];
+            let (spread_lo, spread_hi) = self.spread(&row.coefficients, open.clone());
+            let at_most = remainder + spread_hi;
+            let at_least = remainder + spread_lo - row.scale * self.upper[row.pivot];
+
+            match c.signum() {
+                1 => {
+                    lo = lo.max(ceil_div(at_least, c));
+                    hi = hi.min(floor_div(at_most, c));
+                }
+                -1 => {
+                    lo = lo.max(ceil_div(at_most, c));
+                    hi = hi.min(floor_div(at_least, c));
+                }
+                _ if at_most < 0 || at_least > 0 => return,
+                _ => (),
+            }
+        }
+
+        let c = form.objective.coefficients[depth];
+        let (objective_lo, _) = self.spread(&form.objective.coefficients, open);
+        if let Some((best, _)) = self.best {
+            let limit = objective + objective_lo - best;
+            match c.signum() {
+                1 => lo = lo.max(floor_div(limit, c) + 1),
+                -1 => hi = hi.min(ceil_div(limit, c) - 1),
+                _ if limit >= 0 => return,
+                _ => (),
+            }
+        }
+
+        // Values in order of increasing objective, so the first one that cannot win ends the loop.
+        for i in 0..=hi - lo {
+            let value = if c > 0 { hi - i } else { lo + i };
+            let next = objective - c * value;
+            if self.best.as_ref().is_some_and(|&(best, _)| next + objective_lo >= best) {
+                break;
+            }
+
+            self.x[form.free[depth]] = value;
+            form.fix(depth, value, &mut self.remainders);
+            self.branch(depth + 1, next);
+            form.fix(depth, -value, &mut self.remainders);
+        }
+    }
+}
+
+#[inline]
+fn floor_div(a: i64, b: i64) -> i64 {
+    if b < 0 { (-a).div_euclid(-b) } else { a.div_euclid(b) }
+}
+
+#[inline]
+fn ceil_div(a: i64, b: i64) -> i64 {
+    -floor_div(-a, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Machines from the day 10 example, as button wirings and joltage targets.
+    fn machines() -> Vec<(Vec<Vec<usize>>, Vec<i64>)> {
+        vec![
+            (
+                vec![vec![3], vec![1, 3], vec![2], vec![2, 3], vec![0, 2], vec![0, 1]],
+                vec![3, 5, 4, 7],
+            ),
+            (
+                vec![vec![0, 2, 3, 4], vec![2, 3], vec![0, 4], vec![0, 1, 2], vec![1, 2, 3, 4]],
+                vec![7, 5, 12, 7, 2],
+            ),
+            (
+                vec![vec![0, 1, 2, 3, 4], vec![0, 3, 4], vec![0, 1, 2, 4, 5], vec![1, 2]],
+                vec![10, 11, 11, 5, 10, 5],
+            ),
+        ]
+    }
+
+    fn matrix(buttons: &[Vec<usize>], counters: usize) -> Vec<Vec<i64>> {
+        (0..counters)
+            .map(|j| buttons.iter().map(|button| button.contains(&j) as i64).collect())
+            .collect()
+    }
+
+    fn multiply(a: &[Vec<i64>], x: &[i64]) -> Vec<i64> {
+        a.iter().map(|row| row.iter().zip(x).map(|(a, x)| a * x).sum()).collect()
+    }
+
+    #[test]
+    fn test_rational() {
+        let half = Rational::new(2, -4);
+        assert_eq!((half.numerator(), half.denominator()), (-1, 2));
+        assert_eq!(half + Rational::new(1, 2), Rational::ZERO);
+        assert_eq!(half * Rational::from(-4), Rational::from(2));
+        assert_eq!(Rational::new(1, 3) / Rational::new(2, 3), Rational::new(1, 2));
+        assert!(Rational::new(1, 3) < Rational::new(1, 2));
+        assert_eq!(Rational::new(6, 4).to_string(), "3/2");
+    }
+
+    #[test]
+    #[should_panic(expected = "Rational overflows i64")]
+    fn test_rational_overflow() {
+        let _ = Rational::from(i64::MAX) * Rational::from(2);
+    }
+
+    #[test]
+    fn test_nullspace() {
+        for (buttons, targets) in machines() {
+            let a = matrix(&buttons, targets.len());
+            let system = LinearSystem::new(&a, &targets);
+
+            assert!(system.is_consistent());
+            assert_eq!(system.rank() + system.free_variables().len(), buttons.len());
+            for v in system.nullspace() {
+                assert!(multiply(&a, &v).iter().all(|&n| n == 0));
+            }
+        }
+
+        let system = LinearSystem::new(&[vec![1, 1], vec![1, 1]], &[1, 2]);
+        assert!(!system.is_consistent());
+        assert_eq!(system.solution(&[]), None);
+    }
+
+    #[test]
+    fn test_integer_solutions() {
+        let (buttons, targets) = &machines()[0];
+        let a = matrix(buttons, targets.len());
+        let system = LinearSystem::new(&a, targets);
+        let ranges = vec![0..=7; system.free_variables().len()];
+
+        let mut count = 0;
+        system.for_each_integer_solution(&ranges, |x| {
+            assert_eq!(&multiply(&a, x), targets);
+            count += 1;
+        });
+        assert!(count > 0);
+    }
+
+    #[test]
+    fn test_minimize() {
+        let expected = [10, 12, 11];
+
+        for ((buttons, targets), expected) in machines().into_iter().zip(expected) {
+            let a = matrix(&buttons, targets.len());
+            let upper: Vec<_> =
+                buttons.iter().map(|b| b.iter().map(|&j| targets[j]).min().unwrap()).collect();
+
+            let (presses, x) = minimize(&vec![1; buttons.len()], &a, &targets, &upper).unwrap();
+            assert_eq!(presses, expected);
+            assert_eq!(x.iter().sum::<i64>(), expected);
+            assert_eq!(multiply(&a, &x), targets);
+        }
+    }
+}
@@ -1,4 +1,24 @@
+pub mod bigint;
 pub mod bignumbers;
+pub mod combinatorics;
+pub mod compress;
+pub mod cycle;
 pub mod direction;
+pub mod gf2;
+pub mod graph;
 pub mod grid;
+pub mod grid3;
+pub mod hex;
+pub mod input;
+pub mod interval_set;
+pub mod kdtree;
+pub mod linear;
 pub mod math;
+pub mod point3;
+pub mod polygon;
+pub mod scan;
+pub mod search;
+pub mod sparse_grid;
+#[cfg(test)]
+mod testing;
+pub mod union_find;
@@ -0,0 +1,189 @@
+//! 3-dimensional counterpart of [`Point`] with the same operators, used together with
+//! [`Grid3`].
+//!
+//! Unlike [`Point`] the coordinates are always `i32`. Points mostly index a dense [`Grid3`],
+//! which could never hold more voxels than that, and [`distance_squared`] is computed in `i64`
+//! so that scattered points with large coordinates can still be compared.
+//!
+//! [`distance_squared`]: Point3::distance_squared
+//! [`Point`]: crate::maneatingape::point::Point
+//! [`Grid3`]: crate::majcn::grid3::Grid3
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, AddAssign, Mul, Sub, SubAssign};
+
+pub const ORIGIN_3D: Point3 = Point3::new(0, 0, 0);
+pub const ORTHOGONAL_3D: [Point3; 6] = [
+    Point3::new(-1, 0, 0),
+    Point3::new(1, 0, 0),
+    Point3::new(0, -1, 0),
+    Point3::new(0, 1, 0),
+    Point3::new(0, 0, -1),
+    Point3::new(0, 0, 1),
+];
+// Every offset in the surrounding 3x3x3 cube except the center, ordered by z, then y, then x.
+pub const DIAGONAL_3D: [Point3; 26] = {
+    let mut result = [ORIGIN_3D; 26];
+    let mut i = 0;
+    let mut z = -1;
+    while z <= 1 {
+        let mut y = -1;
+        while y <= 1 {
+            let mut x = -1;
+            while x <= 1 {
+                if x != 0 || y != 0 || z != 0 {
+                    result[i] = Point3::new(x, y, z);
+                    i += 1;
+                }
+                x += 1;
+            }
+            y += 1;
+        }
+        z += 1;
+    }
+    result
+};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Point3 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl Point3 {
+    #[inline]
+    #[must_use]
+    pub const fn new(x: i32, y: i32, z: i32) -> Self {
+        Point3 { x, y, z }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn manhattan(self, other: Self) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    /// Squared Euclidean distance, computed in `i64`. This can not overflow as long as every
+    /// coordinate is within `±2²⁹`.
+    #[inline]
+    #[must_use]
+    pub fn distance_squared(self, other: Self) -> i64 {
+        let dx = self.x as i64 - other.x as i64;
+        let dy = self.y as i64 - other.y as i64;
+        let dz = self.z as i64 - other.z as i64;
+        dx * dx + dy * dy + dz * dz
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn signum(self, other: Self) -> Self {
+        Point3::new(
+            (self.x - other.x).signum(),
+            (self.y - other.y).signum(),
+            (self.z - other.z).signum(),
+        )
+    }
+}
+
+impl Hash for Point3 {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u32(self.x as u32);
+        state.write_u32(self.y as u32);
+        state.write_u32(self.z as u32);
+    }
+}
+
+impl Add for Point3 {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Point3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl AddAssign for Point3 {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+    }
+}
+
+impl Mul<i32> for Point3 {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: i32) -> Self {
+        Point3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Point3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl SubAssign for Point3 {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_operators() {
+        let a = Point3::new(1, -2, 3);
+        let b = Point3::new(-4, 5, 6);
+        assert_eq!(a + b, Point3::new(-3, 3, 9));
+        assert_eq!(a - b, Point3::new(5, -7, -3));
+        assert_eq!(a * 3, Point3::new(3, -6, 9));
+        assert_eq!(a.signum(b), Point3::new(1, -1, -1));
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+
+        assert_eq!(a.manhattan(b), 15);
+        assert_eq!(a.distance_squared(b), 83);
+    }
+
+    #[test]
+    fn test_distance_squared_large() {
+        let limit = 1 << 29;
+        let a = Point3::new(-limit, -limit, -limit);
+        let b = Point3::new(limit, limit, limit);
+        assert_eq!(a.distance_squared(b), 3 << 60);
+
+        // The difference of the coordinates alone does not fit in an `i32`.
+        let a = Point3::new(-(1 << 30), 0, 0);
+        let b = Point3::new((1 << 30) + 5, 0, 0);
+        assert_eq!(a.distance_squared(b), ((1i64 << 31) + 5).pow(2));
+    }
+
+    #[test]
+    fn test_stencils() {
+        let orthogonal: HashSet<_> = ORTHOGONAL_3D.into_iter().collect();
+        assert_eq!(orthogonal.len(), 6);
+        assert!(orthogonal.iter().all(|&p| p.manhattan(ORIGIN_3D) == 1));
+
+        let diagonal: HashSet<_> = DIAGONAL_3D.into_iter().collect();
+        assert_eq!(diagonal.len(), 26);
+        assert!(!diagonal.contains(&ORIGIN_3D));
+        assert!(diagonal.is_superset(&orthogonal));
+    }
+}
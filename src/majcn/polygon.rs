@@ -0,0 +1,223 @@
+//! Simple polygons with integer vertices.
+//!
+//! The free functions work on any simple polygon given as a slice of its vertices in order:
+//!
+//! * [`double_area`] with the [shoelace formula](https://en.wikipedia.org/wiki/Shoelace_formula),
+//!   doubled so that it stays an integer.
+//! * [`boundary_points`] and [`interior_points`] count lattice points on and strictly inside the
+//!   polygon, the latter with [Pick's theorem](https://en.wikipedia.org/wiki/Pick%27s_theorem).
+//! * [`contains`] tests a single point with ray casting, counting the boundary as inside.
+//!
+//! [`RectilinearPolygon`] is for polygons whose edges are all horizontal or vertical. It
+//! [compresses] the vertex coordinates into a small grid where every cell is either completely
+//! inside or completely outside, and keeps [prefix sums] of the lattice points in the outside
+//! cells. This answers whether every lattice point of an axis-aligned rectangle lies inside the
+//! polygon in `O(log n)`. Gaps between adjacent coordinates such as `5` and `6` hold no lattice
+//! points, so they never count as outside even where the continuous polygon would.
+//!
+//! [compresses]: crate::majcn::compress::Compressor
+//! [prefix sums]: crate::majcn::compress::PrefixSums
+use crate::majcn::compress::*;
+use crate::majcn::search::*;
+use crate::maneatingape::math::*;
+use crate::maneatingape::point::*;
+
+/// Twice the area enclosed by `vertices`.
+pub fn double_area(vertices: &[Point64]) -> i64 {
+    edges(vertices).map(|(a, b)| a.x * b.y - a.y * b.x).sum::<i64>().abs()
+}
+
+/// Number of lattice points on the edges of the polygon.
+pub fn boundary_points(vertices: &[Point64]) -> i64 {
+    edges(vertices).map(|(a, b)| (b.x - a.x).abs().gcd((b.y - a.y).abs())).sum()
+}
+
+/// Number of lattice points strictly inside the polygon.
+pub fn interior_points(vertices: &[Point64]) -> i64 {
+    (double_area(vertices) - boundary_points(vertices) + 2) / 2
+}
+
+/// Whether `point` is inside the polygon or on its boundary.
+pub fn contains(vertices: &[Point64], point: Point64) -> bool {
+    let mut inside = false;
+
+    for (a, b) in edges(vertices) {
+        let cross = (b.x - a.x) * (point.y - a.y) - (point.x - a.x) * (b.y - a.y);
+        let within_x = a.x.min(b.x) <= point.x && point.x <= a.x.max(b.x);
+        let within_y = a.y.min(b.y) <= point.y && point.y <= a.y.max(b.y);
+        if cross == 0 && within_x && within_y {
+            return true;
+        }
+
+        if (a.y > point.y) != (b.y > point.y) && (cross > 0) == (b.y > a.y) {
+            inside = !inside;
+        }
+    }
+
+    inside
+}
+
+#[inline]
+fn edges(vertices: &[Point64]) -> impl Iterator<Item = (Point64, Point64)> + '_ {
+    vertices.iter().copied().zip(vertices.iter().copied().cycle().skip(1))
+}
+
+pub struct RectilinearPolygon {
+    pub vertices: Vec<Point64>,
+    walls: CompressedGrid<bool, i64>,
+    outside: PrefixSums,
+}
+
+impl RectilinearPolygon {
+    /// Panics if an edge is neither horizontal nor vertical.
+    pub fn new(vertices: Vec<Point64>) -> Self {
+        assert!(
+            edges(&vertices).all(|(a, b)| a.x == b.x || a.y == b.y),
+            "Edges of a rectilinear polygon must be horizontal or vertical"
+        );
+
+        let xs = Compressor::new(vertices.iter().map(|p| p.x));
+        let ys = Compressor::new(vertices.iter().map(|p| p.y));
+
+        let mut walls = CompressedGrid::new(xs, ys, false);
+        for (a, b) in edges(&vertices) {
+            let (a, b) = (walls.cell(a), walls.cell(b));
+            for y in a.y.min(b.y)..=a.y.max(b.y) {
+                for x in a.x.min(b.x)..=a.x.max(b.x) {
+                    walls[Point::new(x, y)] = true;
+                }
+            }
+        }
+
+        // The unbounded cells around the edge of the compressed grid connect the whole outside.
+        let distances = grid_bfs(&walls.grid, ORIGIN, |wall| !wall);
+        let outside = PrefixSums::areas(&walls, |point, _| (distances[point] != u32::MAX) as i64);
+
+        RectilinearPolygon { vertices, walls, outside }
+    }
+
+    #[inline]
+    pub fn double_area(&self) -> i64 {
+        double_area(&self.vertices)
+    }
+
+    #[inline]
+    pub fn boundary_points(&self) -> i64 {
+        boundary_points(&self.vertices)
+    }
+
+    #[inline]
+    pub fn interior_points(&self) -> i64 {
+        interior_points(&self.vertices)
+    }
+
+    /// Whether `point` is inside the polygon or on its boundary.
+    #[inline]
+    pub fn contains(&self, point: Point64) -> bool {
+        self.contains_rectangle(point, point)
+    }
+
+    /// Whether every lattice point of the rectangle with opposite corners `a` and `b` lies
+    /// inside the polygon, where the boundary counts as inside.
+    pub fn contains_rectangle(&self, a: Point64, b: Point64) -> bool {
+        let min = Point64::new(a.x.min(b.x), a.y.min(b.y));
+        let max = Point64::new(a.x.max(b.x), a.y.max(b.y));
+
+        // The unbounded cells around the polygon have no size, so check them separately.
+        let (xs, ys) = (self.walls.xs.values(), self.walls.ys.values());
+        let (Some(&left), Some(&right), Some(&top), Some(&bottom)) =
+            (xs.first(), xs.last(), ys.first(), ys.last())
+        else {
+            return false;
+        };
+        if min.x < left || max.x > right || min.y < top || max.y > bottom {
+            return false;
+        }
+
+        self.outside.sum(self.walls.cell(min), self.walls.cell(max)) == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polygon(vertices: &[(i64, i64)]) -> Vec<Point64> {
+        vertices.iter().map(|&(x, y)| Point64::new(x, y)).collect()
+    }
+
+    fn examples() -> Vec<Vec<Point64>> {
+        let square = polygon(&[(0, 0), (4, 0), (4, 3), (0, 3)]);
+        let l_shape = polygon(&[(0, 0), (6, 0), (6, 2), (2, 2), (2, 5), (0, 5)]);
+        let reversed = l_shape.iter().rev().copied().collect();
+        let cross = polygon(&[
+            (2, 0),
+            (4, 0),
+            (4, 2),
+            (6, 2),
+            (6, 4),
+            (4, 4),
+            (4, 6),
+            (2, 6),
+            (2, 4),
+            (0, 4),
+            (0, 2),
+            (2, 2),
+        ]);
+        // The slot between x = 5 and x = 6 is outside, but holds no lattice points.
+        let gap = polygon(&[(0, 0), (11, 0), (11, 10), (6, 10), (6, 2), (5, 2), (5, 10), (0, 10)]);
+        vec![square, l_shape, reversed, cross, gap]
+    }
+
+    #[test]
+    fn test_lattice_points() {
+        for vertices in examples() {
+            let inside = (-1..13)
+                .flat_map(|y| (-1..13).map(move |x| Point64::new(x, y)))
+                .filter(|&p| contains(&vertices, p))
+                .count() as i64;
+            assert_eq!(interior_points(&vertices) + boundary_points(&vertices), inside);
+        }
+
+        let gap = &examples()[4];
+        assert_eq!(double_area(gap), 2 * (110 - 8));
+        assert_eq!(boundary_points(gap), 58);
+    }
+
+    #[test]
+    fn test_contains_rectangle() {
+        for vertices in examples() {
+            let rectilinear = RectilinearPolygon::new(vertices.clone());
+            let inside: Vec<Vec<bool>> = (-1..13)
+                .map(|y| (-1..13).map(|x| contains(&vertices, Point64::new(x, y))).collect())
+                .collect();
+
+            for y1 in -1..13 {
+                for x1 in -1..13 {
+                    let a = Point64::new(x1, y1);
+                    assert_eq!(
+                        rectilinear.contains(a),
+                        inside[(y1 + 1) as usize][(x1 + 1) as usize]
+                    );
+
+                    for y2 in y1..13 {
+                        for x2 in -1..13 {
+                            let b = Point64::new(x2, y2);
+                            let expected = (y1..=y2).all(|y| {
+                                (x1.min(x2)..=x1.max(x2))
+                                    .all(|x| inside[(y + 1) as usize][(x + 1) as usize])
+                            });
+                            assert_eq!(rectilinear.contains_rectangle(a, b), expected);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "horizontal or vertical")]
+    fn test_diagonal_edge() {
+        RectilinearPolygon::new(polygon(&[(0, 0), (4, 0), (0, 3)]));
+    }
+}
@@ -0,0 +1,218 @@
+//! Runtime support for the `scan!` macro from `advent_of_code_macros`.
+//!
+//! The macro splits a format such as `"{}: {}"` into literals and captures at compile time and
+//! expands into straight-line calls to the functions below, so a line is matched with plain byte
+//! comparisons. Each capture is converted with [`FromScan`], either to the type given inline
+//! (`"{u32}"`) or to the type inferred from the surrounding code:
+//!
+//! ```
+//!   # use advent_of_code_macros::scan;
+//!
+//!   let (name, outputs): (&str, &str) = scan!("aaa: bbb ccc", "{}: {}").unwrap();
+//!   assert_eq!((name, outputs), ("aaa", "bbb ccc"));
+//!
+//!   let (x, y) = scan!("p=3,-4", "p={u32},{i32}").unwrap();
+//!   assert_eq!((x, y), (3, -4));
+//! ```
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanError {
+    /// The input did not contain `expected` at byte `position`.
+    Literal { expected: &'static str, position: usize },
+    /// No `expected` delimiter was found after byte `position`.
+    Delimiter { expected: &'static str, position: usize },
+    /// The format was fully matched but input remains from byte `position`.
+    TrailingInput { position: usize },
+    /// Capture number `field` could not be converted to `type_name`.
+    Value { field: usize, value: String, type_name: &'static str },
+}
+
+impl Error for ScanError {}
+
+impl Display for ScanError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScanError::Literal { expected, position } => {
+                write!(f, "expected \"{expected}\" at byte {position}")
+            }
+            ScanError::Delimiter { expected, position } => {
+                write!(f, "expected \"{expected}\" after byte {position}")
+            }
+            ScanError::TrailingInput { position } => {
+                write!(f, "unexpected trailing input at byte {position}")
+            }
+            ScanError::Value { field, value, type_name } => {
+                write!(f, "capture {field} \"{value}\" is not a valid {type_name}")
+            }
+        }
+    }
+}
+
+/// Conversion from a captured slice of the input.
+pub trait FromScan<'a>: Sized {
+    fn from_scan(s: &'a str) -> Option<Self>;
+}
+
+impl<'a> FromScan<'a> for &'a str {
+    #[inline]
+    fn from_scan(s: &'a str) -> Option<Self> {
+        Some(s)
+    }
+}
+
+impl FromScan<'_> for String {
+    #[inline]
+    fn from_scan(s: &str) -> Option<Self> {
+        Some(String::from(s))
+    }
+}
+
+impl FromScan<'_> for char {
+    #[inline]
+    fn from_scan(s: &str) -> Option<Self> {
+        let mut chars = s.chars();
+        let c = chars.next()?;
+        chars.next().is_none().then_some(c)
+    }
+}
+
+macro_rules! from_scan_unsigned {
+    ($($t:ty)*) => ($(
+        impl FromScan<'_> for $t {
+            #[inline]
+            fn from_scan(s: &str) -> Option<Self> {
+                let bytes = s.as_bytes();
+                if bytes.is_empty() {
+                    return None;
+                }
+
+                bytes.iter().try_fold(0 as $t, |n, &b| {
+                    let digit = b.wrapping_sub(b'0');
+                    if digit < 10 { n.checked_mul(10)?.checked_add(digit as $t) } else { None }
+                })
+            }
+        }
+    )*)
+}
+
+macro_rules! from_scan_signed {
+    ($($t:ty)*) => ($(
+        impl FromScan<'_> for $t {
+            #[inline]
+            fn from_scan(s: &str) -> Option<Self> {
+                let (negative, digits) = match s.as_bytes() {
+                    [b'-', rest @ ..] => (true, rest),
+                    [b'+', rest @ ..] => (false, rest),
+                    rest => (false, rest),
+                };
+                if digits.is_empty() {
+                    return None;
+                }
+
+                digits.iter().try_fold(0 as $t, |n, &b| {
+                    let digit = b.wrapping_sub(b'0');
+                    if digit >= 10 {
+                        return None;
+                    }
+                    let n = n.checked_mul(10)?;
+                    if negative { n.checked_sub(digit as $t) } else { n.checked_add(digit as $t) }
+                })
+            }
+        }
+    )*)
+}
+
+from_scan_unsigned!(u8 u16 u32 u64 u128 usize);
+from_scan_signed!(i8 i16 i32 i64 i128 isize);
+
+#[inline]
+pub fn literal(input: &str, pos: &mut usize, expected: &'static str) -> Result<(), ScanError> {
+    if input.as_bytes()[*pos..].starts_with(expected.as_bytes()) {
+        *pos += expected.len();
+        Ok(())
+    } else {
+        Err(ScanError::Literal { expected, position: *pos })
+    }
+}
+
+#[inline]
+pub fn until<'a>(
+    input: &'a str,
+    pos: &mut usize,
+    delimiter: &'static str,
+) -> Result<&'a str, ScanError> {
+    let haystack = &input.as_bytes()[*pos..];
+    let needle = delimiter.as_bytes();
+
+    let offset = if let [b] = needle {
+        haystack.iter().position(|c| c == b)
+    } else {
+        haystack.windows(needle.len()).position(|w| w == needle)
+    };
+
+    let Some(offset) = offset else {
+        return Err(ScanError::Delimiter { expected: delimiter, position: *pos });
+    };
+
+    let result = &input[*pos..*pos + offset];
+    *pos += offset;
+    Ok(result)
+}
+
+#[inline]
+pub fn rest<'a>(input: &'a str, pos: &mut usize) -> &'a str {
+    let result = &input[*pos..];
+    *pos = input.len();
+    result
+}
+
+#[inline]
+pub fn end(input: &str, pos: usize) -> Result<(), ScanError> {
+    if pos == input.len() { Ok(()) } else { Err(ScanError::TrailingInput { position: pos }) }
+}
+
+#[inline]
+pub fn value<'a, T: FromScan<'a>>(field: usize, raw: &'a str) -> Result<T, ScanError> {
+    T::from_scan(raw).ok_or_else(|| ScanError::Value {
+        field,
+        value: String::from(raw),
+        type_name: std::any::type_name::<T>(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_of_code_macros::scan;
+
+    #[test]
+    fn test_scan() {
+        let (x, y, z): (i32, u8, &str) = scan!("move -3 to 7: left", "move {} to {}: {}").unwrap();
+        assert_eq!((x, y, z), (-3, 7, "left"));
+
+        let c = scan!("[x]", "[{char}]").unwrap();
+        assert_eq!(c, 'x');
+        assert_eq!(scan!("{7}", "{{{u64}}}"), Ok(7));
+    }
+
+    #[test]
+    fn test_scan_errors() {
+        let error = scan!("pos=1", "p=({u32})").unwrap_err();
+        assert_eq!(error, ScanError::Literal { expected: "p=(", position: 0 });
+
+        let error = scan!("1,2", "{u32}-{u32}").unwrap_err();
+        assert_eq!(error, ScanError::Delimiter { expected: "-", position: 0 });
+
+        let error = scan!("(1) ", "({u32})").unwrap_err();
+        assert_eq!(error, ScanError::TrailingInput { position: 3 });
+
+        let error = scan!("a=1, b=x", "a={u32}, b={u32}").unwrap_err();
+        assert_eq!(error, ScanError::Value { field: 1, value: "x".to_string(), type_name: "u32" });
+        assert_eq!(error.to_string(), "capture 1 \"x\" is not a valid u32");
+
+        assert!(scan!("300", "{u8}").is_err());
+        assert!(scan!("ab", "{char}").is_err());
+    }
+}
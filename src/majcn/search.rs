@@ -0,0 +1,390 @@
+//! Graph searches that are generic over a neighbor closure, so that nodes can be anything from a
+//! [`Point`] to a whole puzzle state.
+//!
+//! * [`bfs`] for unweighted graphs.
+//! * [`dijkstra`] and [`astar`] for graphs with non-negative edge costs, built on [`MinHeap`].
+//! * [`bfs_bidirectional`] searches from both ends at once, which visits far fewer nodes when
+//!   the graph branches heavily. Edges must be symmetric.
+//!
+//! The first three stop as soon as `is_goal` returns `true` and pass `|_| false` to explore
+//! everything reachable. They return a [`SearchResult`] with the distance and predecessor of
+//! every visited node, from which paths can be reconstructed.
+//!
+//! For the common case of a [`Grid`] there are [`grid_bfs`] and [`grid_dijkstra`], that keep
+//! their state in grids instead of hash maps and return the distance to every cell.
+//!
+//! [`Point`]: crate::maneatingape::point::Point
+//! [`MinHeap`]: crate::maneatingape::heap::MinHeap
+use crate::majcn::grid::*;
+use crate::maneatingape::grid::*;
+use crate::maneatingape::hash::*;
+use crate::maneatingape::heap::*;
+use crate::maneatingape::point::*;
+use std::collections::VecDeque;
+use std::collections::hash_map::Entry;
+use std::hash::Hash;
+use std::ops::Add;
+
+pub struct SearchResult<N, C> {
+    pub distances: FastMap<N, C>,
+    pub predecessors: FastMap<N, N>,
+    /// The goal that stopped the search, if any.
+    pub goal: Option<N>,
+}
+
+impl<N: Copy + Eq + Hash, C: Copy> SearchResult<N, C> {
+    fn new(start: N, zero: C) -> Self {
+        SearchResult {
+            distances: FastMap::build([(start, zero)]),
+            predecessors: FastMap::new(),
+            goal: None,
+        }
+    }
+
+    #[inline]
+    pub fn distance(&self, node: N) -> Option<C> {
+        self.distances.get(&node).copied()
+    }
+
+    /// Nodes from the start to `node` inclusive.
+    pub fn path_to(&self, node: N) -> Option<Vec<N>> {
+        self.distances.get(&node)?;
+
+        let mut path = vec![node];
+        let mut current = node;
+        while let Some(&previous) = self.predecessors.get(&current) {
+            path.push(previous);
+            current = previous;
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+    /// Nodes from the start to the goal inclusive.
+    #[inline]
+    pub fn path(&self) -> Option<Vec<N>> {
+        self.goal.and_then(|goal| self.path_to(goal))
+    }
+}
+
+pub fn bfs<N, I>(
+    start: N,
+    mut neighbors: impl FnMut(N) -> I,
+    mut is_goal: impl FnMut(N) -> bool,
+) -> SearchResult<N, usize>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut result = SearchResult::new(start, 0);
+    let mut todo = VecDeque::from([start]);
+
+    while let Some(node) = todo.pop_front() {
+        if is_goal(node) {
+            result.goal = Some(node);
+            break;
+        }
+
+        let distance = result.distances[&node] + 1;
+        for next in neighbors(node) {
+            if let Entry::Vacant(entry) = result.distances.entry(next) {
+                entry.insert(distance);
+                result.predecessors.insert(next, node);
+                todo.push_back(next);
+            }
+        }
+    }
+
+    result
+}
+
+pub fn dijkstra<N, C, I>(
+    start: N,
+    neighbors: impl FnMut(N) -> I,
+    is_goal: impl FnMut(N) -> bool,
+) -> SearchResult<N, C>
+where
+    N: Copy + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, neighbors, |_| C::default(), is_goal)
+}
+
+/// Same as [`dijkstra`] but explores nodes in order of distance plus `heuristic`, which must
+/// never overestimate the remaining cost.
+pub fn astar<N, C, I>(
+    start: N,
+    mut neighbors: impl FnMut(N) -> I,
+    mut heuristic: impl FnMut(N) -> C,
+    mut is_goal: impl FnMut(N) -> bool,
+) -> SearchResult<N, C>
+where
+    N: Copy + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut result = SearchResult::new(start, C::default());
+    let mut todo = MinHeap::new();
+    todo.push(heuristic(start), (start, C::default()));
+
+    while let Some((_, (node, cost))) = todo.pop() {
+        if cost > result.distances[&node] {
+            continue;
+        }
+        if is_goal(node) {
+            result.goal = Some(node);
+            break;
+        }
+
+        for (next, edge) in neighbors(node) {
+            let next_cost = cost + edge;
+            if result.distances.get(&next).is_none_or(|&c| next_cost < c) {
+                result.distances.insert(next, next_cost);
+                result.predecessors.insert(next, node);
+                todo.push(next_cost + heuristic(next), (next, next_cost));
+            }
+        }
+    }
+
+    result
+}
+
+/// Shortest path from `start` to `goal` inclusive, expanding whichever frontier is smaller.
+pub fn bfs_bidirectional<N, I>(
+    start: N,
+    goal: N,
+    mut neighbors: impl FnMut(N) -> I,
+) -> Option<Vec<N>>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    if start == goal {
+        return Some(vec![start]);
+    }
+
+    let mut forward = FastMap::build([(start, (start, 0))]);
+    let mut backward = FastMap::build([(goal, (goal, 0))]);
+    let mut forward_frontier = vec![start];
+    let mut backward_frontier = vec![goal];
+    let mut meet = None;
+
+    // Finish the layer where the searches first meet, as a later node in the same layer
+    // can still lead to a shorter path.
+    while meet.is_none() && !forward_frontier.is_empty() && !backward_frontier.is_empty() {
+        let (this, frontier, other) = if forward_frontier.len() <= backward_frontier.len() {
+            (&mut forward, &mut forward_frontier, &backward)
+        } else {
+            (&mut backward, &mut backward_frontier, &forward)
+        };
+
+        for node in std::mem::take(frontier) {
+            let depth = this[&node].1 + 1;
+            for next in neighbors(node) {
+                if this.contains_key(&next) {
+                    continue;
+                }
+                this.insert(next, (node, depth));
+
+                if let Some(&(_, other_depth)) = other.get(&next) {
+                    if meet.is_none_or(|(_, best)| depth + other_depth < best) {
+                        meet = Some((next, depth + other_depth));
+                    }
+                } else {
+                    frontier.push(next);
+                }
+            }
+        }
+    }
+
+    let (meet, _) = meet?;
+    let walk = |map: &FastMap<N, (N, usize)>| {
+        let mut path = vec![meet];
+        let mut current = meet;
+        while map[&current].0 != current {
+            current = map[&current].0;
+            path.push(current);
+        }
+        path
+    };
+
+    let mut path = walk(&forward);
+    path.reverse();
+    path.extend(walk(&backward).into_iter().skip(1));
+    Some(path)
+}
+
+/// Steps from `start` to every cell of the grid moving orthogonally through cells where
+/// `is_open` holds, or `u32::MAX` if a cell is unreachable.
+pub fn grid_bfs<T: Copy>(grid: &Grid<T>, start: Point, is_open: impl Fn(T) -> bool) -> Grid<u32> {
+    let mut distances = grid.same_size_with(u32::MAX);
+    let mut todo = VecDeque::from([start]);
+    distances[start] = 0;
+
+    while let Some(point) = todo.pop_front() {
+        let distance = distances[point] + 1;
+        for next in grid.orthogonal_neighbors(point) {
+            if is_open(grid[next]) && distances[next] == u32::MAX {
+                distances[next] = distance;
+                todo.push_back(next);
+            }
+        }
+    }
+
+    distances
+}
+
+/// Lowest total cost from `start` to every cell of the grid moving orthogonally, where `cost`
+/// returns the cost to step from the first point to the second or `None` if the step is not
+/// allowed. Unreachable cells are `u32::MAX`.
+pub fn grid_dijkstra<T: Copy>(
+    grid: &Grid<T>,
+    start: Point,
+    cost: impl Fn(Point, Point) -> Option<u32>,
+) -> Grid<u32> {
+    let mut distances = grid.same_size_with(u32::MAX);
+    let mut todo = MinHeap::new();
+    distances[start] = 0;
+    todo.push(0, start);
+
+    while let Some((distance, point)) = todo.pop() {
+        if distance > distances[point] {
+            continue;
+        }
+
+        for next in grid.orthogonal_neighbors(point) {
+            if let Some(step) = cost(point, next) {
+                let next_distance = distance + step;
+                if next_distance < distances[next] {
+                    distances[next] = next_distance;
+                    todo.push(next_distance, next);
+                }
+            }
+        }
+    }
+
+    distances
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAZE: &str = "S.#...\n.##.#.\n....#G";
+
+    fn open_neighbors(grid: &Grid<u8>) -> impl Fn(Point) -> Vec<Point> + '_ {
+        |point| grid.orthogonal_neighbors(point).filter(|&n| grid[n] != b'#').collect()
+    }
+
+    fn is_valid_path(grid: &Grid<u8>, path: &[Point]) -> bool {
+        path.windows(2).all(|w| w[0].manhattan(w[1]) == 1) && path.iter().all(|&p| grid[p] != b'#')
+    }
+
+    #[test]
+    fn test_bfs() {
+        let grid = Grid::parse(MAZE);
+        let (start, goal) = (grid.find(b'S').unwrap(), grid.find(b'G').unwrap());
+
+        let result = bfs(start, open_neighbors(&grid), |p| p == goal);
+        assert_eq!(result.goal, Some(goal));
+        assert_eq!(result.distance(goal), Some(11));
+
+        let path = result.path().unwrap();
+        let expected = [
+            (0, 0),
+            (0, 1),
+            (0, 2),
+            (1, 2),
+            (2, 2),
+            (3, 2),
+            (3, 1),
+            (3, 0),
+            (4, 0),
+            (5, 0),
+            (5, 1),
+            (5, 2),
+        ];
+        assert_eq!(path, expected.map(|(x, y)| Point::new(x, y)));
+        assert_eq!(result.path_to(start), Some(vec![start]));
+
+        let distances = grid_bfs(&grid, start, |b| b != b'#');
+        assert_eq!(distances[goal], 11);
+        assert_eq!(distances[Point::new(2, 0)], u32::MAX);
+        let everything = bfs(start, open_neighbors(&grid), |_| false);
+        assert!(
+            grid.points()
+                .all(|p| everything.distance(p).map_or(u32::MAX, |d| d as u32) == distances[p])
+        );
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let grid = Grid::parse("S.#..\n..#.G");
+        let (start, goal) = (grid.find(b'S').unwrap(), grid.find(b'G').unwrap());
+
+        let result = bfs(start, open_neighbors(&grid), |p| p == goal);
+        assert_eq!((result.goal, result.distance(goal), result.path()), (None, None, None));
+        assert_eq!(result.path_to(goal), None);
+        assert_eq!(result.distances.len(), 4);
+
+        let result = dijkstra(
+            start,
+            |p| open_neighbors(&grid)(p).into_iter().map(|n| (n, 1)),
+            |p| p == goal,
+        );
+        assert_eq!(result.goal, None);
+        assert_eq!(bfs_bidirectional(start, goal, open_neighbors(&grid)), None);
+        assert_eq!(grid_bfs(&grid, start, |b| b != b'#')[goal], u32::MAX);
+    }
+
+    #[test]
+    fn test_weighted() {
+        let grid = Grid::parse(
+            "1163751742\n1381373672\n2136511328\n3694931569\n7463417111\n\
+             1319128137\n1359912421\n3125421639\n1293138521\n2311944581",
+        );
+        let start = ORIGIN;
+        let cost = |p: Point| (grid[p] - b'0') as u32;
+        let neighbors = |p: Point| grid.orthogonal_neighbors(p).map(move |n| (n, cost(n)));
+
+        let all = dijkstra(start, neighbors, |_| false);
+        let distances = grid_dijkstra(&grid, start, |_, to| Some(cost(to)));
+        assert!(grid.points().all(|p| all.distance(p) == Some(distances[p])));
+
+        for goal in grid.points() {
+            let heuristic = |p: Point| p.manhattan(goal) as u32;
+            let result = astar(start, neighbors, heuristic, |p| p == goal);
+            assert_eq!(result.distance(goal), Some(distances[goal]));
+
+            let path = result.path().unwrap();
+            assert_eq!((path[0], *path.last().unwrap()), (start, goal));
+            assert_eq!(path[1..].iter().map(|&p| cost(p)).sum::<u32>(), distances[goal]);
+        }
+        assert_eq!(distances[Point::new(9, 9)], 40);
+    }
+
+    #[test]
+    fn test_bfs_bidirectional() {
+        let grid = Grid::parse("..#....\n.##.#.#\n...#...\n.#...#.\n...#...");
+
+        for start in grid.points_where(|&b| b != b'#') {
+            let distances = bfs(start, open_neighbors(&grid), |_| false);
+            for goal in grid.points_where(|&b| b != b'#') {
+                let path = bfs_bidirectional(start, goal, open_neighbors(&grid));
+                match distances.distance(goal) {
+                    Some(distance) => {
+                        let path = path.unwrap();
+                        assert_eq!(path.len(), distance + 1);
+                        assert_eq!((path[0], *path.last().unwrap()), (start, goal));
+                        assert!(is_valid_path(&grid, &path));
+                    }
+                    None => assert_eq!(path, None),
+                }
+            }
+        }
+
+        assert_eq!(bfs_bidirectional(ORIGIN, ORIGIN, |_| Vec::new()), Some(vec![ORIGIN]));
+    }
+}
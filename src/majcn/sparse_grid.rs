@@ -0,0 +1,224 @@
+//! Unbounded grid keyed by [`Point`] for simulations that grow outward or use negative
+//! coordinates. Cells that were never set read as the default value. The bounding box of all
+//! set cells is kept up to date, so the grid can be converted to a dense [`Grid`] or printed.
+use crate::maneatingape::grid::*;
+use crate::maneatingape::hash::*;
+use crate::maneatingape::point::*;
+use std::fmt::{Display, Formatter};
+use std::ops::Index;
+
+#[derive(Clone, Debug)]
+pub struct SparseGrid<T> {
+    cells: FastMap<Point, T>,
+    default: T,
+    min: Point,
+    max: Point,
+}
+
+impl<T: Copy> SparseGrid<T> {
+    pub fn new(default: T) -> Self {
+        SparseGrid {
+            cells: FastMap::new(),
+            default,
+            min: Point::new(i32::MAX, i32::MAX),
+            max: Point::new(i32::MIN, i32::MIN),
+        }
+    }
+
+    /// Copies every cell of `grid` that is not equal to `default`.
+    pub fn from_grid(grid: &Grid<T>, default: T) -> Self
+    where
+        T: PartialEq,
+    {
+        let mut sparse = Self::new(default);
+        for (i, &value) in grid.bytes.iter().enumerate() {
+            if value != default {
+                let point = Point::new(i as i32 % grid.width, i as i32 / grid.width);
+                sparse.insert(point, value);
+            }
+        }
+        sparse
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    #[inline]
+    pub fn contains(&self, point: Point) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    #[inline]
+    pub fn get(&self, point: Point) -> T {
+        self.cells.get(&point).copied().unwrap_or(self.default)
+    }
+
+    /// Returns the cell at `point`, setting it to the default value first if it is unset.
+    #[inline]
+    pub fn get_mut(&mut self, point: Point) -> &mut T {
+        self.extend_bounds(point);
+        self.cells.entry(point).or_insert(self.default)
+    }
+
+    #[inline]
+    pub fn insert(&mut self, point: Point, value: T) -> Option<T> {
+        self.extend_bounds(point);
+        self.cells.insert(point, value)
+    }
+
+    pub fn remove(&mut self, point: Point) -> Option<T> {
+        let result = self.cells.remove(&point);
+
+        let on_edge = point.x == self.min.x
+            || point.x == self.max.x
+            || point.y == self.min.y
+            || point.y == self.max.y;
+        if result.is_some() && on_edge {
+            self.min = Point::new(i32::MAX, i32::MAX);
+            self.max = Point::new(i32::MIN, i32::MIN);
+            let points: Vec<_> = self.cells.keys().copied().collect();
+            points.into_iter().for_each(|p| self.extend_bounds(p));
+        }
+
+        result
+    }
+
+    /// Top left and bottom right corners (inclusive) of all set cells.
+    #[inline]
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        (!self.is_empty()).then_some((self.min, self.max))
+    }
+
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (Point, T)> + '_ {
+        self.cells.iter().map(|(&point, &value)| (point, value))
+    }
+
+    /// Every neighbor in `stencil` with its value, unset cells included.
+    #[inline]
+    pub fn neighbors<'a>(
+        &'a self,
+        point: Point,
+        stencil: &'a [Point],
+    ) -> impl Iterator<Item = (Point, T)> + 'a {
+        stencil.iter().map(move |&d| (point + d, self.get(point + d)))
+    }
+
+    /// Only the neighbors in `stencil` that are set.
+    #[inline]
+    pub fn set_neighbors<'a>(
+        &'a self,
+        point: Point,
+        stencil: &'a [Point],
+    ) -> impl Iterator<Item = Point> + 'a {
+        stencil.iter().map(move |&d| point + d).filter(|n| self.contains(*n))
+    }
+
+    /// Dense copy of the bounding box along with the position of its top left corner.
+    pub fn to_grid(&self) -> (Grid<T>, Point) {
+        let Some((min, max)) = self.bounds() else {
+            return (Grid { width: 0, height: 0, bytes: vec![] }, ORIGIN);
+        };
+
+        let mut grid = Grid::new(max.x - min.x + 1, max.y - min.y + 1, self.default);
+        for (&point, &value) in &self.cells {
+            grid[point - min] = value;
+        }
+
+        (grid, min)
+    }
+
+    pub fn to_string_with<D: Display>(&self, f: impl Fn(&T) -> D) -> String {
+        self.to_grid().0.display_with(f).to_string()
+    }
+
+    #[inline]
+    fn extend_bounds(&mut self, point: Point) {
+        self.min = Point::new(self.min.x.min(point.x), self.min.y.min(point.y));
+        self.max = Point::new(self.max.x.max(point.x), self.max.y.max(point.y));
+    }
+}
+
+impl<T> Index<Point> for SparseGrid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: Point) -> &Self::Output {
+        self.cells.get(&index).unwrap_or(&self.default)
+    }
+}
+
+impl Display for SparseGrid<u8> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.to_grid().0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounds() {
+        let mut grid = SparseGrid::new(b'.');
+        assert_eq!(grid.bounds(), None);
+
+        grid.insert(Point::new(-2, 1), b'#');
+        grid.insert(Point::new(3, -1), b'#');
+        grid.insert(Point::new(0, 0), b'#');
+        assert_eq!(grid.bounds(), Some((Point::new(-2, -1), Point::new(3, 1))));
+
+        // Removing an inner cell keeps the bounds, removing edge cells shrinks them.
+        grid.remove(Point::new(0, 0));
+        assert_eq!(grid.bounds(), Some((Point::new(-2, -1), Point::new(3, 1))));
+        grid.insert(Point::new(0, 0), b'#');
+        grid.remove(Point::new(3, -1));
+        assert_eq!(grid.bounds(), Some((Point::new(-2, 0), Point::new(0, 1))));
+        grid.remove(Point::new(-2, 1));
+        assert_eq!(grid.bounds(), Some((Point::new(0, 0), Point::new(0, 0))));
+        grid.remove(Point::new(0, 0));
+        assert_eq!(grid.bounds(), None);
+
+        *grid.get_mut(Point::new(5, 5)) = b'#';
+        assert_eq!(grid.bounds(), Some((Point::new(5, 5), Point::new(5, 5))));
+        assert_eq!(grid[Point::new(4, 4)], b'.');
+    }
+
+    #[test]
+    fn test_grid_round_trip() {
+        let dense = Grid::parse("#..\n.#.\n..#\n#..");
+        let sparse = SparseGrid::from_grid(&dense, b'.');
+        assert_eq!(sparse.len(), 4);
+        assert_eq!(sparse.to_grid(), (dense, ORIGIN));
+
+        let mut sparse = SparseGrid::new(b'.');
+        sparse.insert(Point::new(-1, -3), b'#');
+        sparse.insert(Point::new(1, -2), b'o');
+        let (dense, offset) = sparse.to_grid();
+        assert_eq!(offset, Point::new(-1, -3));
+        assert_eq!(dense, Grid::parse("#..\n..o"));
+
+        let empty = SparseGrid::new(0u8).to_grid().0;
+        assert_eq!((empty.width, empty.height), (0, 0));
+    }
+
+    #[test]
+    fn test_display() {
+        let mut grid = SparseGrid::new(b'.');
+        grid.insert(Point::new(10, 10), b'#');
+        grid.insert(Point::new(12, 11), b'#');
+        assert_eq!(grid.to_string(), "#..\n..#");
+
+        let mut grid = SparseGrid::new(false);
+        grid.insert(Point::new(-1, 0), true);
+        grid.insert(Point::new(0, 1), true);
+        assert_eq!(grid.to_string_with(|&on| if on { '█' } else { ' ' }), "█ \n █");
+    }
+}
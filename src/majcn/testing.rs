@@ -0,0 +1,28 @@
+//! Fixtures shared by the unit tests.
+
+/// [Xorshift](https://en.wikipedia.org/wiki/Xorshift) generator for randomized tests.
+///
+/// Every test starts from the same fixed seed, so failures are reproducible.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new() -> Self {
+        Rng(0x2545_f491_4f6c_dd1d)
+    }
+
+    pub fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Uniform enough value in `0..bound` for tests.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound
+    }
+
+    pub fn next_u128(&mut self) -> u128 {
+        (self.next() as u128) << 64 | self.next() as u128
+    }
+}
@@ -23,40 +23,180 @@
 //! creates a grid of the same size that can be used in BFS algorithms for tracking visited
 //! locations or for tracking cost in Dijkstra.
 //!
+//! Rows are contiguous and can be borrowed as slices with [`row`], while [`column`] iterates
+//! down a single column. Whole grid transformations such as [`transpose`], rotations, flips and
+//! [`subgrid`] come in two flavours, one returning a new grid and an `_in_place` variant that
+//! updates `self`.
+//!
+//! Grids of other types are created with [`parse_with`], which maps each byte of the input.
+//! Both parse methods pad ragged input to a rectangle, use [`try_parse`] or [`try_parse_with`] to
+//! reject empty or ragged input instead. A `Grid<u8>` implements [`Display`], any other grid can be
+//! formatted by supplying a per-cell formatter to [`display_with`].
+//!
 //! [`Point`]: crate::util::point
 //! [`parse`]: Grid::parse
 //! [`same_size_with`]: Grid::same_size_with
+//! [`parse_with`]: Grid::parse_with
+//! [`try_parse`]: Grid::try_parse
+//! [`try_parse_with`]: Grid::try_parse_with
+//! [`display_with`]: Grid::display_with
+//! [`row`]: Grid::row
+//! [`column`]: Grid::column
+//! [`transpose`]: Grid::transpose
+//! [`subgrid`]: Grid::subgrid
 use super::point::*;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::iter::{Skip, StepBy};
 use std::ops::{Index, IndexMut};
+use std::slice::{ChunksExact, ChunksExactMut, Iter};
 
-#[derive(Clone, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Grid<T> {
     pub width: i32,
     pub height: i32,
     pub bytes: Vec<T>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    Empty,
+    Ragged { line: usize, expected: usize, actual: usize },
+}
+
+impl Error for GridError {}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::Empty => write!(f, "grid is empty"),
+            GridError::Ragged { line, expected, actual } => {
+                write!(f, "line {line} has length {actual}, expected {expected}")
+            }
+        }
+    }
+}
+
 impl Grid<u8> {
+    /// Parses leniently like [`Grid::parse_with`], except that short rows are padded with spaces.
     #[inline]
     pub fn parse(input: &str) -> Self {
-        let raw: Vec<_> = input.lines().map(str::as_bytes).collect();
-
-        let width = raw[0].len() as i32;
-        let height = raw.len() as i32;
-        let bytes = raw.concat();
+        Self::parse_padded(input, b' ', |b| b)
+    }
 
-        Grid { width, height, bytes }
+    #[inline]
+    pub fn try_parse(input: &str) -> Result<Self, GridError> {
+        Self::try_parse_with(input, |b| b)
     }
 
     pub fn print(&self) {
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let point = Point::new(x, y);
-                print!("{}", self[point] as char);
+        println!("{self}");
+        println!();
+    }
+}
+
+impl<T> Grid<T> {
+    /// Trailing blank lines are ignored and short rows are padded with `T::default()` to the
+    /// width of the longest row, as editors often strip trailing whitespace. The padding is not
+    /// passed through `f`, so mappers such as `|b| b - b'0'` only ever see bytes of the input.
+    /// Use [`Grid::try_parse_with`] to reject such input instead.
+    #[inline]
+    pub fn parse_with(input: &str, f: impl FnMut(u8) -> T) -> Self
+    where
+        T: Default + Clone,
+    {
+        Self::parse_padded(input, T::default(), f)
+    }
+
+    fn parse_padded(input: &str, padding: T, mut f: impl FnMut(u8) -> T) -> Self
+    where
+        T: Clone,
+    {
+        let raw: Vec<_> = input.trim_end_matches(['\n', '\r']).lines().map(str::as_bytes).collect();
+        let width = raw.iter().map(|line| line.len()).max().unwrap_or(0);
+
+        let mut bytes = Vec::with_capacity(width * raw.len());
+        for line in &raw {
+            bytes.extend(line.iter().map(|&b| f(b)));
+            bytes.resize(bytes.len() + width - line.len(), padding.clone());
+        }
+
+        Grid { width: width as i32, height: raw.len() as i32, bytes }
+    }
+
+    /// Like [`Grid::parse_with`] trailing blank lines are ignored, but every other row must
+    /// have the same non-zero length.
+    pub fn try_parse_with(input: &str, f: impl FnMut(u8) -> T) -> Result<Self, GridError> {
+        let raw: Vec<_> = input.trim_end_matches(['\n', '\r']).lines().map(str::as_bytes).collect();
+
+        let width = raw.first().map_or(0, |line| line.len());
+        if width == 0 {
+            return Err(GridError::Empty);
+        }
+        if let Some((line, row)) = raw.iter().enumerate().find(|(_, row)| row.len() != width) {
+            return Err(GridError::Ragged { line, expected: width, actual: row.len() });
+        }
+
+        let bytes = raw.concat().into_iter().map(f).collect();
+
+        Ok(Grid { width: width as i32, height: raw.len() as i32, bytes })
+    }
+
+    /// Formats each cell with `f`, writing one line per row.
+    #[inline]
+    pub fn display_with<D: Display, F: Fn(&T) -> D>(&self, f: F) -> GridDisplay<'_, T, F> {
+        GridDisplay { grid: self, f }
+    }
+}
+
+pub struct GridDisplay<'a, T, F> {
+    grid: &'a Grid<T>,
+    f: F,
+}
+
+impl<T, D: Display, F: Fn(&T) -> D> Display for GridDisplay<'_, T, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.grid.height {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in self.grid.row(y) {
+                write!(f, "{}", (self.f)(cell))?;
             }
-            println!();
         }
-        println!();
+        Ok(())
+    }
+}
+
+impl Display for Grid<u8> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.display_with(|&b| b as char).fmt(f)
+    }
+}
+
+impl<T> TryFrom<Vec<Vec<T>>> for Grid<T> {
+    type Error = GridError;
+
+    fn try_from(rows: Vec<Vec<T>>) -> Result<Self, Self::Error> {
+        let width = rows.first().map_or(0, Vec::len);
+        if width == 0 {
+            return Err(GridError::Empty);
+        }
+        if let Some((line, row)) = rows.iter().enumerate().find(|(_, row)| row.len() != width) {
+            return Err(GridError::Ragged { line, expected: width, actual: row.len() });
+        }
+
+        let height = rows.len() as i32;
+        let bytes = rows.into_iter().flatten().collect();
+
+        Ok(Grid { width: width as i32, height, bytes })
+    }
+}
+
+impl<T> From<Grid<T>> for Vec<Vec<T>> {
+    fn from(grid: Grid<T>) -> Self {
+        let mut bytes = grid.bytes.into_iter();
+        (0..grid.height).map(|_| bytes.by_ref().take(grid.width as usize).collect()).collect()
     }
 }
 
@@ -75,6 +215,82 @@ impl<T: Copy> Grid<T> {
     pub fn new(width: i32, height: i32, value: T) -> Grid<T> {
         Grid { width, height, bytes: vec![value; (width * height) as usize] }
     }
+
+    /// Builds a `width` by `height` grid where each cell is taken from `self` at the point
+    /// returned by `source`.
+    #[inline]
+    fn remap(&self, width: i32, height: i32, source: impl Fn(i32, i32) -> Point) -> Grid<T> {
+        let mut bytes = Vec::with_capacity((width * height) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                bytes.push(self[source(x, y)]);
+            }
+        }
+        Grid { width, height, bytes }
+    }
+
+    /// Swaps rows and columns, so that the cell at `(x, y)` moves to `(y, x)`.
+    pub fn transpose(&self) -> Grid<T> {
+        self.remap(self.height, self.width, |x, y| Point::new(y, x))
+    }
+
+    pub fn rotate_clockwise(&self) -> Grid<T> {
+        self.remap(self.height, self.width, |x, y| Point::new(y, self.height - 1 - x))
+    }
+
+    pub fn rotate_counter_clockwise(&self) -> Grid<T> {
+        self.remap(self.height, self.width, |x, y| Point::new(self.width - 1 - y, x))
+    }
+
+    pub fn rotate_180(&self) -> Grid<T> {
+        let mut grid = self.clone();
+        grid.rotate_180_in_place();
+        grid
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_horizontal(&self) -> Grid<T> {
+        let mut grid = self.clone();
+        grid.flip_horizontal_in_place();
+        grid
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_vertical(&self) -> Grid<T> {
+        let mut grid = self.clone();
+        grid.flip_vertical_in_place();
+        grid
+    }
+
+    /// Copies the `width` by `height` rectangle whose top left corner is `origin`. An empty
+    /// rectangle gives an empty grid wherever it is.
+    pub fn subgrid(&self, origin: Point, width: i32, height: i32) -> Grid<T> {
+        assert!(width >= 0 && height >= 0, "Subgrid size must not be negative");
+        if width == 0 || height == 0 {
+            return Grid { width, height, bytes: Vec::new() };
+        }
+        assert!(
+            self.contains(origin) && self.contains(origin + Point::new(width - 1, height - 1)),
+            "Subgrid is out of bounds"
+        );
+        self.remap(width, height, |x, y| origin + Point::new(x, y))
+    }
+
+    pub fn transpose_in_place(&mut self) {
+        *self = self.transpose();
+    }
+
+    pub fn rotate_clockwise_in_place(&mut self) {
+        *self = self.rotate_clockwise();
+    }
+
+    pub fn rotate_counter_clockwise_in_place(&mut self) {
+        *self = self.rotate_counter_clockwise();
+    }
+
+    pub fn subgrid_in_place(&mut self, origin: Point, width: i32, height: i32) {
+        *self = self.subgrid(origin, width, height);
+    }
 }
 
 impl<T> Grid<T> {
@@ -91,6 +307,58 @@ impl<T> Grid<T> {
     pub fn contains(&self, point: Point) -> bool {
         point.x >= 0 && point.x < self.width && point.y >= 0 && point.y < self.height
     }
+
+    #[inline]
+    pub fn row(&self, y: i32) -> &[T] {
+        let start = (self.width * y) as usize;
+        &self.bytes[start..start + self.width as usize]
+    }
+
+    #[inline]
+    pub fn row_mut(&mut self, y: i32) -> &mut [T] {
+        let start = (self.width * y) as usize;
+        &mut self.bytes[start..start + self.width as usize]
+    }
+
+    /// A grid without columns has no cells, so it yields no rows.
+    #[inline]
+    pub fn rows(&self) -> ChunksExact<'_, T> {
+        self.bytes.chunks_exact(self.width.max(1) as usize)
+    }
+
+    #[inline]
+    pub fn rows_mut(&mut self) -> ChunksExactMut<'_, T> {
+        self.bytes.chunks_exact_mut(self.width.max(1) as usize)
+    }
+
+    /// Iterates top to bottom over the column at `x`.
+    #[inline]
+    pub fn column(&self, x: i32) -> StepBy<Skip<Iter<'_, T>>> {
+        assert!(x >= 0 && x < self.width, "Column {x} is out of bounds");
+        self.bytes.iter().skip(x as usize).step_by(self.width as usize)
+    }
+
+    #[inline]
+    pub fn columns(&self) -> impl Iterator<Item = StepBy<Skip<Iter<'_, T>>>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn rotate_180_in_place(&mut self) {
+        self.bytes.reverse();
+    }
+
+    pub fn flip_horizontal_in_place(&mut self) {
+        self.rows_mut().for_each(<[T]>::reverse);
+    }
+
+    pub fn flip_vertical_in_place(&mut self) {
+        let width = self.width as usize;
+        let height = self.height as usize;
+        for y in 0..height / 2 {
+            let (top, bottom) = self.bytes.split_at_mut((height - 1 - y) * width);
+            top[y * width..(y + 1) * width].swap_with_slice(&mut bottom[..width]);
+        }
+    }
 }
 
 impl<T> Index<Point> for Grid<T> {
@@ -108,3 +376,119 @@ impl<T> IndexMut<Point> for Grid<T> {
         &mut self.bytes[(self.width * index.y + index.x) as usize]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = "abc\ndef";
+
+    fn rows(grid: &Grid<u8>) -> Vec<&[u8]> {
+        grid.rows().collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse_with(FIXTURE, |b| b - b'a');
+        assert_eq!((grid.width, grid.height), (3, 2));
+        assert_eq!(grid.bytes, [0, 1, 2, 3, 4, 5]);
+
+        assert_eq!(Grid::try_parse(""), Err(GridError::Empty));
+        assert_eq!(
+            Grid::try_parse("abc\nde\nf"),
+            Err(GridError::Ragged { line: 1, expected: 3, actual: 2 })
+        );
+
+        let padded = Grid::parse("ab\nabc\na\n\n");
+        assert_eq!((padded.width, padded.height), (3, 3));
+        assert_eq!(padded.to_string(), "ab \nabc\na  ");
+        assert_eq!(Grid::parse("").rows().count(), 0);
+
+        let digits = Grid::parse_with("12\n3", |b| b - b'0');
+        assert_eq!(digits.bytes, [1, 2, 3, 0]);
+
+        let trailing = Grid::try_parse("ab\r\ncd\r\n\r\n").unwrap();
+        assert_eq!(trailing, Grid::parse("ab\ncd"));
+        assert_eq!(Grid::try_parse("\n\n"), Err(GridError::Empty));
+        assert_eq!(
+            Grid::try_parse("ab\n\ncd"),
+            Err(GridError::Ragged { line: 1, expected: 2, actual: 0 })
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let grid = Grid::parse(FIXTURE);
+        assert_eq!(grid.to_string(), FIXTURE);
+
+        let grid = Grid::parse_with(".#\n#.", |b| b == b'#');
+        assert_eq!(grid.display_with(|&on| if on { 'X' } else { ' ' }).to_string(), " X\nX ");
+    }
+
+    #[test]
+    fn test_vec_conversion() {
+        let grid = Grid::parse(FIXTURE);
+        let rows: Vec<Vec<u8>> = grid.clone().into();
+        assert_eq!(rows, [b"abc".to_vec(), b"def".to_vec()]);
+        assert_eq!(Grid::try_from(rows), Ok(grid));
+        assert_eq!(
+            Grid::try_from(vec![vec![1], vec![]]).unwrap_err().to_string(),
+            "line 1 has length 0, expected 1"
+        );
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = Grid::parse(FIXTURE);
+        assert_eq!(grid.row(1), b"def");
+        assert_eq!(rows(&grid), [b"abc", b"def"]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), b"be");
+        assert_eq!(grid.columns().count(), 3);
+    }
+
+    #[test]
+    fn test_transpose() {
+        let grid = Grid::parse(FIXTURE);
+        assert_eq!(grid.transpose(), Grid::parse("ad\nbe\ncf"));
+        assert_eq!(grid.transpose().transpose(), grid);
+    }
+
+    #[test]
+    fn test_rotate() {
+        let grid = Grid::parse(FIXTURE);
+        assert_eq!(grid.rotate_clockwise(), Grid::parse("da\neb\nfc"));
+        assert_eq!(grid.rotate_counter_clockwise(), Grid::parse("cf\nbe\nad"));
+        assert_eq!(grid.rotate_180(), Grid::parse("fed\ncba"));
+        assert_eq!(grid.rotate_clockwise().rotate_clockwise(), grid.rotate_180());
+
+        let mut in_place = grid.clone();
+        in_place.rotate_counter_clockwise_in_place();
+        in_place.rotate_clockwise_in_place();
+        assert_eq!(in_place, grid);
+    }
+
+    #[test]
+    fn test_flip() {
+        let grid = Grid::parse(FIXTURE);
+        assert_eq!(grid.flip_horizontal(), Grid::parse("cba\nfed"));
+        assert_eq!(grid.flip_vertical(), Grid::parse("def\nabc"));
+
+        let mut in_place = Grid::parse("ab\ncd\nef");
+        in_place.flip_vertical_in_place();
+        assert_eq!(in_place, Grid::parse("ef\ncd\nab"));
+    }
+
+    #[test]
+    fn test_subgrid() {
+        let grid = Grid::parse("abcd\nefgh\nijkl");
+        assert_eq!(grid.subgrid(Point::new(1, 1), 2, 2), Grid::parse("fg\njk"));
+
+        let mut in_place = grid.clone();
+        in_place.subgrid_in_place(Point::new(3, 0), 1, 3);
+        assert_eq!(in_place, Grid::parse("d\nh\nl"));
+
+        let empty = grid.subgrid(Point::new(4, 3), 0, 2);
+        assert_eq!((empty.width, empty.height), (0, 2));
+        assert!(empty.bytes.is_empty());
+    }
+}
@@ -25,6 +25,11 @@ pub trait Integer<T>:
     const TEN: T;
 
     fn trailing_zeros(self) -> u32;
+
+    /// `(self * rhs) % m`, computed in a wider type where one exists so that the product
+    /// can not overflow. `u128` and `i128` are their own wider type, so there the product of
+    /// two values below `m` only fits if `m` fits in 64 bits.
+    fn mul_mod(self, rhs: T, m: T) -> T;
 }
 
 pub trait Unsigned<T>: Integer<T> {}
@@ -32,7 +37,7 @@ pub trait Unsigned<T>: Integer<T> {}
 pub trait Signed<T>: Integer<T> + Neg<Output = T> {}
 
 macro_rules! integer {
-    ($($t:ty)*) => ($(
+    ($($t:ty => $wide:ty),*) => ($(
         impl Integer<$t> for $t {
             const ZERO: $t = 0;
             const ONE: $t = 1;
@@ -42,6 +47,11 @@ macro_rules! integer {
             fn trailing_zeros(self) -> u32 {
                 <$t>::trailing_zeros(self)
             }
+
+            #[inline]
+            fn mul_mod(self, rhs: $t, m: $t) -> $t {
+                (self as $wide * rhs as $wide % m as $wide) as $t
+            }
         }
     )*)
 }
@@ -52,6 +62,9 @@ macro_rules! empty_trait {
     )*)
 }
 
-integer!(u8 u16 u32 u64 u128 usize i16 i32 i64 i128);
+integer!(
+    u8 => u128, u16 => u128, u32 => u128, u64 => u128, u128 => u128, usize => u128,
+    i16 => i128, i32 => i128, i64 => i128, i128 => i128
+);
 empty_trait!(Unsigned for u8 u16 u32 u64 u128 usize);
 empty_trait!(Signed for i16 i32 i64 i128);
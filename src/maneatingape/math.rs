@@ -11,21 +11,42 @@
 //! * [Modular exponentiation](https://en.wikipedia.org/wiki/Modular_exponentiation).
 //!   Calculates bᵉ mod m efficiently using
 //!   [exponentiation by squaring](https://en.wikipedia.org/wiki/Exponentiation_by_squaring).
+//!   Products are computed in a wider type, so any modulus up to `u64::MAX` is safe. `u128` and
+//!   `i128` have no wider type, so their modulus is limited to `u64::MAX` as well.
 //!
 //! * [Modular multiplicative inverse](https://en.wikipedia.org/wiki/Modular_multiplicative_inverse)
 //!   calculated using the [extended Euclidean algorithm](https://en.wikipedia.org/wiki/Extended_Euclidean_algorithm).
 //!
-//! * [Integer square root](https://en.wikipedia.org/wiki/Integer_square_root).
+//! * [Integer square root](https://en.wikipedia.org/wiki/Integer_square_root). Named
+//!   `integer_sqrt` so that the inherent `isqrt` of the primitive types does not shadow it.
+//!
+//! * [Chinese remainder theorem](https://en.wikipedia.org/wiki/Chinese_remainder_theorem)
+//!   combining congruences whose moduli need not be coprime.
+//!
+//! * Prime numbers, using the [sieve of Eratosthenes](https://en.wikipedia.org/wiki/Sieve_of_Eratosthenes)
+//!   for small ranges and the deterministic [Miller–Rabin test](https://en.wikipedia.org/wiki/Miller%E2%80%93Rabin_primality_test)
+//!   for any `u64`. Numbers are factorized with trial division followed by
+//!   [Pollard's rho](https://en.wikipedia.org/wiki/Pollard%27s_rho_algorithm), which in turn
+//!   gives all divisors and [Euler's totient](https://en.wikipedia.org/wiki/Euler%27s_totient_function).
 use super::integer::*;
 
 pub trait IntegerMathOps<T: Integer<T>> {
     fn gcd(self, b: T) -> T;
     fn lcm(self, b: T) -> T;
     fn mod_pow(self, e: T, m: T) -> T;
+    fn integer_sqrt(self) -> T;
 }
 
 pub trait SignedMathOps<T: Signed<T>> {
     fn mod_inv(self, m: T) -> Option<T>;
+    fn extended_gcd(self, b: T) -> (T, T, T);
+}
+
+pub trait PrimeMathOps {
+    fn is_prime(&self) -> bool;
+    fn factorize(self) -> Vec<(u64, u32)>;
+    fn divisors(self) -> Vec<u64>;
+    fn totient(self) -> u64;
 }
 
 impl<T: Integer<T>> IntegerMathOps<T> for T {
@@ -48,6 +69,9 @@ impl<T: Integer<T>> IntegerMathOps<T> for T {
     }
 
     /// Modular exponentiation
+    ///
+    /// For `u128` and `i128` the product of two values below `m` must not overflow, so `m`
+    /// must fit in 64 bits.
     #[inline]
     fn mod_pow(self, mut e: T, m: T) -> T {
         let mut base = self;
@@ -55,14 +79,33 @@ impl<T: Integer<T>> IntegerMathOps<T> for T {
 
         while e > T::ZERO {
             if e & T::ONE == T::ONE {
-                result = (result * base) % m;
+                result = result.mul_mod(base, m);
             }
-            base = (base * base) % m;
+            base = base.mul_mod(base, m);
             e = e >> 1;
         }
 
         result
     }
+
+    /// Integer square root, rounded down, using Newton's method.
+    #[inline]
+    fn integer_sqrt(self) -> T {
+        let two = T::from(2);
+        if self < two {
+            return self;
+        }
+
+        // Starting above the root converges downwards without overflowing.
+        let mut x = self / two + T::ONE;
+        loop {
+            let y = (x + self / x) / two;
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
 }
 
 impl<T: Signed<T>> SignedMathOps<T> for T {
@@ -89,4 +132,276 @@ impl<T: Signed<T>> SignedMathOps<T> for T {
 
         Some(t)
     }
+
+    /// Returns `(g, x, y)` where `g` is the greatest common divisor and `a·x + b·y = g`.
+    #[inline]
+    fn extended_gcd(self, b: T) -> (T, T, T) {
+        let (mut r, mut new_r) = (self, b);
+        let (mut x, mut new_x) = (T::ONE, T::ZERO);
+        let (mut y, mut new_y) = (T::ZERO, T::ONE);
+
+        while new_r != T::ZERO {
+            let quotient = r / new_r;
+            (r, new_r) = (new_r, r - quotient * new_r);
+            (x, new_x) = (new_x, x - quotient * new_x);
+            (y, new_y) = (new_y, y - quotient * new_y);
+        }
+
+        (r, x, y)
+    }
+}
+
+/// Combines congruences `x ≡ rᵢ (mod mᵢ)` into a single `x ≡ r (mod m)` where `m` is the least
+/// common multiple of the moduli and `0 <= r < m`. Returns `None` if the congruences conflict,
+/// which can only happen when the moduli share a factor.
+///
+/// Panics if a modulus is not positive or if `m` does not fit in an `i64`.
+pub fn chinese_remainder(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    let mut r = 0;
+    let mut m = 1;
+
+    for (ri, mi) in congruences {
+        assert!(mi > 0, "Modulus {mi} is not positive");
+        let (ri, mi) = ((ri as i128).rem_euclid(mi as i128), mi as i128);
+        let (g, inverse, _) = m.extended_gcd(mi);
+        if (ri - r) % g != 0 {
+            return None;
+        }
+
+        let step = mi / g;
+        let k = ((ri - r) / g % step * inverse % step).rem_euclid(step);
+        r += m * k;
+        m *= step;
+        assert!(m <= i64::MAX as i128, "Combined modulus overflows i64");
+    }
+
+    Some((r as i64, m as i64))
+}
+
+/// All primes up to and including `limit`.
+pub fn sieve(limit: usize) -> Vec<usize> {
+    let mut composite = vec![false; limit + 1];
+    let mut primes = Vec::new();
+
+    for n in 2..=limit {
+        if !composite[n] {
+            primes.push(n);
+            (n * n..=limit).step_by(n).for_each(|multiple| composite[multiple] = true);
+        }
+    }
+
+    primes
+}
+
+impl PrimeMathOps for u64 {
+    /// Deterministic Miller–Rabin test. The first twelve primes as witnesses are enough for
+    /// every 64 bit number.
+    fn is_prime(&self) -> bool {
+        const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+        let n = *self;
+        if n < 2 {
+            return false;
+        }
+        if let Some(&p) = WITNESSES.iter().find(|&&p| n.is_multiple_of(p)) {
+            return n == p;
+        }
+
+        let s = (n - 1).trailing_zeros();
+        let d = (n - 1) >> s;
+
+        WITNESSES.iter().all(|&a| {
+            let mut x = a.mod_pow(d, n);
+            if x == 1 || x == n - 1 {
+                return true;
+            }
+            (1..s).any(|_| {
+                x = x.mul_mod(x, n);
+                x == n - 1
+            })
+        })
+    }
+
+    /// Prime factors with their multiplicities, in increasing order. Zero has none.
+    fn factorize(self) -> Vec<(u64, u32)> {
+        if self == 0 {
+            return vec![];
+        }
+
+        let mut n = self;
+        let mut primes = Vec::new();
+
+        for p in [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37] {
+            while n.is_multiple_of(p) {
+                primes.push(p);
+                n /= p;
+            }
+        }
+
+        let mut todo = if n > 1 { vec![n] } else { vec![] };
+        while let Some(n) = todo.pop() {
+            if n.is_prime() {
+                primes.push(n);
+            } else {
+                let d = pollard_rho(n);
+                todo.push(d);
+                todo.push(n / d);
+            }
+        }
+
+        primes.sort_unstable();
+        let mut factors: Vec<(u64, u32)> = Vec::new();
+        for p in primes {
+            match factors.last_mut() {
+                Some((q, k)) if *q == p => *k += 1,
+                _ => factors.push((p, 1)),
+            }
+        }
+        factors
+    }
+
+    /// All positive divisors in increasing order.
+    fn divisors(self) -> Vec<u64> {
+        if self == 0 {
+            return vec![];
+        }
+
+        let mut divisors = vec![1];
+        for (p, k) in self.factorize() {
+            let previous = divisors.len();
+            let mut power = 1;
+            for _ in 0..k {
+                power *= p;
+                let start = divisors.len();
+                divisors.extend_from_within(..previous);
+                divisors[start..].iter_mut().for_each(|d| *d *= power);
+            }
+        }
+
+        divisors.sort_unstable();
+        divisors
+    }
+
+    /// Number of integers from `1` to `self` that are coprime to it.
+    fn totient(self) -> u64 {
+        self.factorize().iter().fold(self, |phi, &(p, _)| phi / p * (p - 1))
+    }
+}
+
+/// Finds a non-trivial factor of the odd composite `n` with
+/// [Brent's variant](https://maths-people.anu.edu.au/~brent/pd/rpb051i.pdf) of Pollard's rho,
+/// trying increasing constants in the polynomial `x² + c` until one succeeds.
+fn pollard_rho(n: u64) -> u64 {
+    let next = |x: u64, c: u64| ((x.mul_mod(x, n) as u128 + c as u128) % n as u128) as u64;
+
+    for c in 1.. {
+        let (mut x, mut y) = (2, 2);
+        let mut factor = 1;
+        let mut power = 1;
+        let mut length = 1;
+
+        while factor == 1 {
+            if power == length {
+                x = y;
+                power *= 2;
+                length = 0;
+            }
+            y = next(y, c);
+            length += 1;
+            factor = x.abs_diff(y).gcd(n);
+        }
+
+        if factor != n {
+            return factor;
+        }
+    }
+
+    unreachable!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LARGEST_PRIME: u64 = 18_446_744_073_709_551_557;
+
+    #[test]
+    fn test_chinese_remainder() {
+        assert_eq!(chinese_remainder([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(chinese_remainder([(2, 6), (4, 8)]), Some((20, 24)));
+        assert_eq!(chinese_remainder([(1, 6), (4, 8)]), None);
+        assert_eq!(chinese_remainder([(-1, 4)]), Some((3, 4)));
+        assert_eq!(chinese_remainder([]), Some((0, 1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Modulus 0 is not positive")]
+    fn test_chinese_remainder_zero_modulus() {
+        chinese_remainder([(2, 3), (1, 0)]);
+    }
+
+    #[test]
+    fn test_gcd_and_inverse() {
+        assert_eq!(12u64.gcd(18), 6);
+        assert_eq!(4u64.lcm(6), 12);
+        assert_eq!(240i64.extended_gcd(46), (2, -9, 47));
+        assert_eq!(3i64.mod_inv(7), Some(5));
+        assert_eq!(4i64.mod_inv(8), None);
+    }
+
+    #[test]
+    fn test_mod_pow() {
+        assert_eq!(4u64.mod_pow(13, 497), 445);
+        assert_eq!(2u64.mod_pow(LARGEST_PRIME - 1, LARGEST_PRIME), 1);
+        assert_eq!(
+            3u128.mod_pow(u64::MAX as u128 - 1, u64::MAX as u128),
+            3u64.mod_pow(u64::MAX - 1, u64::MAX) as u128
+        );
+    }
+
+    #[test]
+    fn test_integer_sqrt() {
+        for n in (0..10_000u64).chain([u32::MAX as u64, u64::MAX - 1, u64::MAX]) {
+            assert_eq!(n.integer_sqrt(), n.isqrt());
+        }
+        assert_eq!(u128::MAX.integer_sqrt(), u64::MAX as u128);
+    }
+
+    #[test]
+    fn test_is_prime() {
+        let primes = sieve(10_000);
+        assert!((0..=10_000).all(|n| (n as u64).is_prime() == primes.binary_search(&n).is_ok()));
+
+        // Carmichael numbers and strong pseudoprimes to small bases.
+        for n in [561, 41_041, 3_215_031_751, 3_825_123_056_546_413_051] {
+            assert!(!n.is_prime());
+        }
+        assert!((u64::MAX >> 3).is_prime());
+        assert!(LARGEST_PRIME.is_prime());
+        assert!(!u64::MAX.is_prime());
+    }
+
+    #[test]
+    fn test_factorize() {
+        let (p, q) = (4_294_967_291, 4_294_967_279);
+        assert_eq!((p * q).factorize(), [(q, 1), (p, 1)]);
+        assert_eq!(LARGEST_PRIME.factorize(), [(LARGEST_PRIME, 1)]);
+        assert_eq!(
+            u64::MAX.factorize(),
+            [(3, 1), (5, 1), (17, 1), (257, 1), (641, 1), (65_537, 1), (6_700_417, 1)]
+        );
+        assert_eq!(360.factorize(), [(2, 3), (3, 2), (5, 1)]);
+        assert_eq!(1.factorize(), []);
+        assert_eq!(0.factorize(), []);
+    }
+
+    #[test]
+    fn test_divisors_and_totient() {
+        for n in 1..=1_000u64 {
+            let divisors: Vec<_> = (1..=n).filter(|d| n.is_multiple_of(*d)).collect();
+            assert_eq!(n.divisors(), divisors);
+            assert_eq!(n.totient(), (1..=n).filter(|k| k.gcd(n) == 1).count() as u64);
+        }
+        assert_eq!(0.divisors(), []);
+    }
 }
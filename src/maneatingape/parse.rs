@@ -9,18 +9,77 @@
 //!   Lorem ipsum 123 dolor 456 sit 789 amet
 //! ```
 //!
-//! This module provides two [`&str`] extension methods [`iter_signed`] and [`iter_unsigned`]. The
+//! This module provides two extension methods [`iter_signed`] and [`iter_unsigned`]. The
 //! reason for the separate methods is that some Advent of Code inputs contain the `-` character
 //! as a delimiter and this would cause numbers to be incorrectly parsed as negative.
 //!
+//! The methods work on anything that can be viewed as bytes, so a [`&str`], a `&[u8]` or a row of
+//! a [`Grid`] can all be parsed directly. Some variations are also available:
+//!
+//! * [`iter_unsigned_radix`] parses numbers in any base from 2 to 36, for example hexadecimal
+//!   or binary. Digits above 9 are the letters `a` to `z`, in either case.
+//! * [`iter_fixed`] splits runs of digits into numbers of at most `width` digits, so that
+//!   `"987"` with a width of 1 returns `9`, `8` and `7`.
+//! * [`with_offsets`] adapts any of the iterators to also return the byte offset where each
+//!   number starts.
+//!
+//! Numbers are built up one digit at a time through [`FromDigits`]. Every primitive integer
+//! implements it, as do the arbitrary-precision [`BigUint`] and [`BigInt`], so values that
+//! overflow `u128` can be parsed the same way.
+//!
+//! ```
+//!   # use advent_of_code::maneatingape::parse::*;
+//!
+//!   let hex: Vec<u32> = "#ff, #10".iter_unsigned_radix(16).collect();
+//!   assert_eq!(hex, [255, 16]);
+//!
+//!   let offsets: Vec<(usize, i32)> = "x=-3 y=12".iter_signed().with_offsets().collect();
+//!   assert_eq!(offsets, [(2, -3), (7, 12)]);
+//! ```
+//!
 //! [`iter_unsigned`]: ParseOps::iter_unsigned
 //! [`iter_signed`]: ParseOps::iter_signed
+//! [`iter_unsigned_radix`]: ParseOps::iter_unsigned_radix
+//! [`iter_fixed`]: ParseOps::iter_fixed
+//! [`with_offsets`]: ParseIterator::with_offsets
+//! [`Grid`]: crate::maneatingape::grid::Grid
+//! [`BigUint`]: crate::majcn::bigint::BigUint
+//! [`BigInt`]: crate::majcn::bigint::BigInt
 use super::integer::*;
 use std::marker::PhantomData;
-use std::str::Bytes;
+use std::ops::Neg;
+
+/// Numbers that can be built up one digit at a time, most significant digit first.
+pub trait FromDigits: Sized {
+    fn from_digit(digit: u8) -> Self;
+    fn push_digit(self, radix: u8, digit: u8) -> Self;
+}
+
+/// Numbers returned by the unsigned parsing methods.
+pub trait UnsignedDigits: FromDigits {}
+
+/// Numbers returned by the signed parsing methods.
+pub trait SignedDigits: FromDigits + Neg<Output = Self> {}
+
+impl<T: Integer<T>> FromDigits for T {
+    #[inline]
+    fn from_digit(digit: u8) -> T {
+        T::from(digit)
+    }
+
+    #[inline]
+    fn push_digit(self, radix: u8, digit: u8) -> T {
+        T::from(radix) * self + T::from(digit)
+    }
+}
+
+impl<T: Unsigned<T>> UnsignedDigits for T {}
+
+impl<T: Signed<T>> SignedDigits for T {}
 
 pub trait ParseByte {
     fn to_decimal(self) -> u8;
+    fn to_digit(self) -> u8;
 }
 
 impl ParseByte for u8 {
@@ -28,112 +87,286 @@ impl ParseByte for u8 {
     fn to_decimal(self) -> u8 {
         self.wrapping_sub(b'0')
     }
+
+    /// Digit value in bases up to 36, or `u8::MAX` if the byte is not alphanumeric.
+    #[inline]
+    fn to_digit(self) -> u8 {
+        match self {
+            b'0'..=b'9' => self - b'0',
+            b'a'..=b'z' => self - b'a' + 10,
+            b'A'..=b'Z' => self - b'A' + 10,
+            _ => u8::MAX,
+        }
+    }
 }
 
 pub struct ParseUnsigned<'a, T> {
-    bytes: Bytes<'a>,
+    bytes: &'a [u8],
+    index: usize,
     phantom: PhantomData<T>,
 }
 
 pub struct ParseSigned<'a, T> {
-    bytes: Bytes<'a>,
+    bytes: &'a [u8],
+    index: usize,
+    phantom: PhantomData<T>,
+}
+
+pub struct ParseRadix<'a, T> {
+    bytes: &'a [u8],
+    index: usize,
+    radix: u8,
+    phantom: PhantomData<T>,
+}
+
+pub struct ParseFixed<'a, T> {
+    bytes: &'a [u8],
+    index: usize,
+    width: usize,
     phantom: PhantomData<T>,
 }
 
 pub trait ParseOps {
-    fn unsigned<T: Unsigned<T>>(&self) -> T;
-    fn signed<T: Signed<T>>(&self) -> T;
-    fn iter_unsigned<T: Unsigned<T>>(&self) -> ParseUnsigned<'_, T>;
-    fn iter_signed<T: Signed<T>>(&self) -> ParseSigned<'_, T>;
+    fn unsigned<T: UnsignedDigits>(&self) -> T;
+    fn signed<T: SignedDigits>(&self) -> T;
+    fn unsigned_radix<T: UnsignedDigits>(&self, radix: u8) -> T;
+    fn iter_unsigned<T: UnsignedDigits>(&self) -> ParseUnsigned<'_, T>;
+    fn iter_signed<T: SignedDigits>(&self) -> ParseSigned<'_, T>;
+    fn iter_unsigned_radix<T: UnsignedDigits>(&self, radix: u8) -> ParseRadix<'_, T>;
+    fn iter_fixed<T: UnsignedDigits>(&self, width: usize) -> ParseFixed<'_, T>;
 }
 
-impl<S: AsRef<str>> ParseOps for S {
-    fn unsigned<T: Unsigned<T>>(&self) -> T {
-        let str = self.as_ref();
-        try_unsigned(&mut str.bytes()).unwrap_or_else(|| panic!("Unable to parse \"{str}\""))
+impl<S: AsRef<[u8]> + ?Sized> ParseOps for S {
+    fn unsigned<T: UnsignedDigits>(&self) -> T {
+        let bytes = self.as_ref();
+        try_unsigned(bytes, &mut 0)
+            .map(|(_, n)| n)
+            .unwrap_or_else(|| panic!("Unable to parse \"{}\"", String::from_utf8_lossy(bytes)))
     }
 
-    fn signed<T: Signed<T>>(&self) -> T {
-        let str = self.as_ref();
-        try_signed(&mut str.bytes()).unwrap_or_else(|| panic!("Unable to parse \"{str}\""))
+    fn signed<T: SignedDigits>(&self) -> T {
+        let bytes = self.as_ref();
+        try_signed(bytes, &mut 0)
+            .map(|(_, n)| n)
+            .unwrap_or_else(|| panic!("Unable to parse \"{}\"", String::from_utf8_lossy(bytes)))
     }
 
-    fn iter_unsigned<T: Unsigned<T>>(&self) -> ParseUnsigned<'_, T> {
-        ParseUnsigned { bytes: self.as_ref().bytes(), phantom: PhantomData }
+    fn unsigned_radix<T: UnsignedDigits>(&self, radix: u8) -> T {
+        assert!((2..=36).contains(&radix), "Radix {radix} is not between 2 and 36");
+        let bytes = self.as_ref();
+        try_radix(bytes, &mut 0, radix)
+            .map(|(_, n)| n)
+            .unwrap_or_else(|| panic!("Unable to parse \"{}\"", String::from_utf8_lossy(bytes)))
     }
 
-    fn iter_signed<T: Signed<T>>(&self) -> ParseSigned<'_, T> {
-        ParseSigned { bytes: self.as_ref().bytes(), phantom: PhantomData }
+    fn iter_unsigned<T: UnsignedDigits>(&self) -> ParseUnsigned<'_, T> {
+        ParseUnsigned { bytes: self.as_ref(), index: 0, phantom: PhantomData }
     }
-}
 
-impl<T: Unsigned<T>> Iterator for ParseUnsigned<'_, T> {
-    type Item = T;
+    fn iter_signed<T: SignedDigits>(&self) -> ParseSigned<'_, T> {
+        ParseSigned { bytes: self.as_ref(), index: 0, phantom: PhantomData }
+    }
 
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let (lower, upper) = self.bytes.size_hint();
-        (lower / 3, upper.map(|u| u / 3))
+    fn iter_unsigned_radix<T: UnsignedDigits>(&self, radix: u8) -> ParseRadix<'_, T> {
+        assert!((2..=36).contains(&radix), "Radix {radix} is not between 2 and 36");
+        ParseRadix { bytes: self.as_ref(), index: 0, radix, phantom: PhantomData }
     }
 
-    #[inline]
-    fn next(&mut self) -> Option<Self::Item> {
-        try_unsigned(&mut self.bytes)
+    fn iter_fixed<T: UnsignedDigits>(&self, width: usize) -> ParseFixed<'_, T> {
+        assert!(width > 0, "Width must be at least 1");
+        ParseFixed { bytes: self.as_ref(), index: 0, width, phantom: PhantomData }
     }
 }
 
-impl<T: Signed<T>> Iterator for ParseSigned<'_, T> {
-    type Item = T;
+/// Common interface of the parsing iterators that also exposes where each number was found.
+pub trait ParseIterator: Iterator + Sized {
+    /// Returns the next number along with the byte offset of its first character.
+    fn next_with_offset(&mut self) -> Option<(usize, Self::Item)>;
+
+    fn with_offsets(self) -> WithOffsets<Self> {
+        WithOffsets { iter: self }
+    }
+}
+
+pub struct WithOffsets<I> {
+    iter: I,
+}
+
+impl<I: ParseIterator> Iterator for WithOffsets<I> {
+    type Item = (usize, I::Item);
 
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let (lower, upper) = self.bytes.size_hint();
-        (lower / 3, upper.map(|u| u / 3))
+        self.iter.size_hint()
     }
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        try_signed(&mut self.bytes)
+        self.iter.next_with_offset()
     }
 }
 
-fn try_unsigned<T: Unsigned<T>>(bytes: &mut Bytes<'_>) -> Option<T> {
-    let mut n = loop {
-        let digit = bytes.next()?.to_decimal();
+macro_rules! iterator {
+    ($name:ident, $bound:ident, |$self:ident| $parse:expr) => {
+        impl<T: $bound> Iterator for $name<'_, T> {
+            type Item = T;
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let remaining = self.bytes.len() - self.index;
+                (remaining / 3, Some(remaining))
+            }
+
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                self.next_with_offset().map(|(_, n)| n)
+            }
+        }
+
+        impl<T: $bound> ParseIterator for $name<'_, T> {
+            #[inline]
+            fn next_with_offset(&mut $self) -> Option<(usize, Self::Item)> {
+                $parse
+            }
+        }
+    };
+}
+
+iterator!(ParseUnsigned, UnsignedDigits, |self| try_unsigned(self.bytes, &mut self.index));
+iterator!(ParseSigned, SignedDigits, |self| try_signed(self.bytes, &mut self.index));
+iterator!(ParseRadix, UnsignedDigits, |self| try_radix(self.bytes, &mut self.index, self.radix));
+iterator!(ParseFixed, UnsignedDigits, |self| try_fixed(self.bytes, &mut self.index, self.width));
+
+fn try_unsigned<T: UnsignedDigits>(bytes: &[u8], index: &mut usize) -> Option<(usize, T)> {
+    let (start, mut n) = loop {
+        let digit = bytes.get(*index)?.to_decimal();
+        *index += 1;
         if digit < 10 {
-            break T::from(digit);
+            break (*index - 1, T::from_digit(digit));
         }
     };
 
-    for byte in bytes {
+    while let Some(byte) = bytes.get(*index) {
         let digit = byte.to_decimal();
+        *index += 1;
         if digit >= 10 {
             break;
         }
-        n = T::TEN * n + T::from(digit);
+        n = n.push_digit(10, digit);
     }
 
-    Some(n)
+    Some((start, n))
 }
 
-fn try_signed<T: Signed<T>>(bytes: &mut Bytes<'_>) -> Option<T> {
-    let (mut n, negative) = loop {
-        let digit = bytes.next()?.to_decimal();
+fn try_signed<T: SignedDigits>(bytes: &[u8], index: &mut usize) -> Option<(usize, T)> {
+    let (start, mut n, negative) = loop {
+        let digit = bytes.get(*index)?.to_decimal();
+        *index += 1;
         if digit == 253 {
-            break (T::ZERO, true);
+            break (*index - 1, T::from_digit(0), true);
         }
         if digit < 10 {
-            break (T::from(digit), false);
+            break (*index - 1, T::from_digit(digit), false);
         }
     };
 
-    for byte in bytes {
+    while let Some(byte) = bytes.get(*index) {
         let digit = byte.to_decimal();
+        *index += 1;
         if digit >= 10 {
             break;
         }
-        n = T::TEN * n + T::from(digit);
+        n = n.push_digit(10, digit);
+    }
+
+    Some((start, if negative { -n } else { n }))
+}
+
+fn try_radix<T: UnsignedDigits>(bytes: &[u8], index: &mut usize, radix: u8) -> Option<(usize, T)> {
+    let (start, mut n) = loop {
+        let digit = bytes.get(*index)?.to_digit();
+        *index += 1;
+        if digit < radix {
+            break (*index - 1, T::from_digit(digit));
+        }
+    };
+
+    while let Some(byte) = bytes.get(*index) {
+        let digit = byte.to_digit();
+        *index += 1;
+        if digit >= radix {
+            break;
+        }
+        n = n.push_digit(radix, digit);
+    }
+
+    Some((start, n))
+}
+
+fn try_fixed<T: UnsignedDigits>(
+    bytes: &[u8],
+    index: &mut usize,
+    width: usize,
+) -> Option<(usize, T)> {
+    let (start, mut n) = loop {
+        let digit = bytes.get(*index)?.to_decimal();
+        *index += 1;
+        if digit < 10 {
+            break (*index - 1, T::from_digit(digit));
+        }
+    };
+
+    while *index - start < width {
+        let Some(digit) = bytes.get(*index).map(|b| b.to_decimal()).filter(|&d| d < 10) else {
+            break;
+        };
+        *index += 1;
+        n = n.push_digit(10, digit);
     }
 
-    Some(if negative { -n } else { n })
+    Some((start, n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_radix() {
+        assert_eq!("#1F".unsigned_radix::<u32>(16), 31);
+        assert_eq!("1011".unsigned_radix::<u8>(2), 11);
+        assert_eq!("zz".unsigned_radix::<u32>(36), 1295);
+
+        let hex: Vec<u64> = "#c0ffee, #A1b2 - ff".iter_unsigned_radix(16).collect();
+        assert_eq!(hex, [0xc0ffee, 0xa1b2, 0xff]);
+        let binary: Vec<u8> = "101 2 0110".iter_unsigned_radix(2).collect();
+        assert_eq!(binary, [0b101, 0b0110]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Radix 37 is not between 2 and 36")]
+    fn test_invalid_radix() {
+        "10".unsigned_radix::<u32>(37);
+    }
+
+    #[test]
+    fn test_with_offsets() {
+        let signed: Vec<(usize, i32)> = "-12 -3,4 x-5".iter_signed().with_offsets().collect();
+        assert_eq!(signed, [(0, -12), (4, -3), (7, 4), (10, -5)]);
+
+        let unsigned: Vec<(usize, u32)> = "-12 -3,4".iter_unsigned().with_offsets().collect();
+        assert_eq!(unsigned, [(1, 12), (5, 3), (7, 4)]);
+
+        let hex: Vec<(usize, u32)> = "..ff.1".iter_unsigned_radix(16).with_offsets().collect();
+        assert_eq!(hex, [(2, 255), (5, 1)]);
+    }
+
+    #[test]
+    fn test_fixed() {
+        let pairs: Vec<u32> = "12345 678".iter_fixed(2).collect();
+        assert_eq!(pairs, [12, 34, 5, 67, 8]);
+
+        let digits: Vec<(usize, u8)> = "98,7".iter_fixed(1).with_offsets().collect();
+        assert_eq!(digits, [(0, 9), (1, 8), (3, 7)]);
+    }
 }
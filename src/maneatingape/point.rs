@@ -23,12 +23,20 @@
 //! and a [`manhattan`] function for the
 //! [Manhattan distance](https://en.wikipedia.org/wiki/Taxicab_geometry) between 2 points.
 //!
+//! The coordinate type defaults to `i32`, which is what [`Grid`] uses. Puzzles with coordinates
+//! large enough to overflow can use [`Point64`] or [`Point128`] instead, or the `checked_` and
+//! `wrapping_` variants of the arithmetic operators. Points are ordered top to bottom, then left
+//! to right, the same order as [`DIAGONAL`].
+//!
 //! [`clockwise`]: Point::clockwise
 //! [`counter_clockwise`]: Point::counter_clockwise
 //! [`manhattan`]: Point::manhattan
 //! [`Grid`]: crate::util::grid
-use std::hash::{Hash, Hasher};
-use std::ops::{Add, AddAssign, Mul, Sub, SubAssign};
+use super::integer::*;
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
 
 pub const ORIGIN: Point = Point::new(0, 0);
 pub const UP: Point = Point::new(0, -1);
@@ -48,19 +56,84 @@ pub const DIAGONAL: [Point; 8] = [
     Point::new(1, 1),
 ];
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
-pub struct Point {
-    pub x: i32,
-    pub y: i32,
+pub type Point64 = Point<i64>;
+pub type Point128 = Point<i128>;
+
+/// Signed integer types that can be used as coordinates.
+pub trait Coordinate: Signed<Self> + Ord + Hash + Debug {
+    fn abs(self) -> Self;
+    fn signum(self) -> Self;
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn wrapping_add(self, rhs: Self) -> Self;
+    fn wrapping_sub(self, rhs: Self) -> Self;
+    fn wrapping_mul(self, rhs: Self) -> Self;
 }
 
-impl Point {
+macro_rules! coordinate {
+    ($($t:ty)*) => ($(
+        impl Coordinate for $t {
+            #[inline]
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+
+            #[inline]
+            fn signum(self) -> Self {
+                <$t>::signum(self)
+            }
+
+            #[inline]
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            #[inline]
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_sub(self, rhs)
+            }
+
+            #[inline]
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+
+            #[inline]
+            fn wrapping_add(self, rhs: Self) -> Self {
+                <$t>::wrapping_add(self, rhs)
+            }
+
+            #[inline]
+            fn wrapping_sub(self, rhs: Self) -> Self {
+                <$t>::wrapping_sub(self, rhs)
+            }
+
+            #[inline]
+            fn wrapping_mul(self, rhs: Self) -> Self {
+                <$t>::wrapping_mul(self, rhs)
+            }
+        }
+    )*)
+}
+
+coordinate!(i16 i32 i64 i128);
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Point<T = i32> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
     #[inline]
     #[must_use]
-    pub const fn new(x: i32, y: i32) -> Self {
+    pub const fn new(x: T, y: T) -> Self {
         Point { x, y }
     }
+}
 
+impl<T: Coordinate> Point<T> {
     #[inline]
     #[must_use]
     pub fn clockwise(self) -> Self {
@@ -73,41 +146,92 @@ impl Point {
         Point::new(self.y, -self.x)
     }
 
+    /// Rotates 90 degrees clockwise around `center` instead of the origin.
     #[inline]
     #[must_use]
-    pub fn manhattan(self, other: Self) -> i32 {
+    pub fn clockwise_around(self, center: Self) -> Self {
+        (self - center).clockwise() + center
+    }
+
+    /// Rotates 90 degrees counter clockwise around `center` instead of the origin.
+    #[inline]
+    #[must_use]
+    pub fn counter_clockwise_around(self, center: Self) -> Self {
+        (self - center).counter_clockwise() + center
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn manhattan(self, other: Self) -> T {
         (self.x - other.x).abs() + (self.y - other.y).abs()
     }
 
+    /// [Chebyshev distance](https://en.wikipedia.org/wiki/Chebyshev_distance), the number of
+    /// king moves between 2 points.
+    #[inline]
+    #[must_use]
+    pub fn chebyshev(self, other: Self) -> T {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
     #[inline]
     #[must_use]
     pub fn signum(self, other: Self) -> Self {
         Point::new((self.x - other.x).signum(), (self.y - other.y).signum())
     }
+
+    #[inline]
+    #[must_use]
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        Some(Point::new(self.x.checked_add(rhs.x)?, self.y.checked_add(rhs.y)?))
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        Some(Point::new(self.x.checked_sub(rhs.x)?, self.y.checked_sub(rhs.y)?))
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn checked_mul(self, rhs: T) -> Option<Self> {
+        Some(Point::new(self.x.checked_mul(rhs)?, self.y.checked_mul(rhs)?))
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn wrapping_add(self, rhs: Self) -> Self {
+        Point::new(self.x.wrapping_add(rhs.x), self.y.wrapping_add(rhs.y))
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn wrapping_sub(self, rhs: Self) -> Self {
+        Point::new(self.x.wrapping_sub(rhs.x), self.y.wrapping_sub(rhs.y))
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn wrapping_mul(self, rhs: T) -> Self {
+        Point::new(self.x.wrapping_mul(rhs), self.y.wrapping_mul(rhs))
+    }
 }
 
-impl From<u8> for Point {
+impl<T: Coordinate> PartialOrd for Point<T> {
     #[inline]
-    fn from(value: u8) -> Self {
-        match value {
-            b'^' | b'U' => UP,
-            b'v' | b'D' => DOWN,
-            b'<' | b'L' => LEFT,
-            b'>' | b'R' => RIGHT,
-            _ => unreachable!(),
-        }
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
-impl Hash for Point {
+impl<T: Coordinate> Ord for Point<T> {
     #[inline]
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        state.write_u32(self.x as u32);
-        state.write_u32(self.y as u32);
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.y.cmp(&other.y).then(self.x.cmp(&other.x))
     }
 }
 
-impl Add for Point {
+impl<T: Coordinate> Add for Point<T> {
     type Output = Self;
 
     #[inline]
@@ -116,24 +240,41 @@ impl Add for Point {
     }
 }
 
-impl AddAssign for Point {
+impl<T: Coordinate> AddAssign for Point<T> {
     #[inline]
     fn add_assign(&mut self, rhs: Self) {
-        self.x += rhs.x;
-        self.y += rhs.y;
+        *self = *self + rhs;
     }
 }
 
-impl Mul<i32> for Point {
+impl<T: Coordinate> Mul<T> for Point<T> {
     type Output = Self;
 
     #[inline]
-    fn mul(self, rhs: i32) -> Self {
+    fn mul(self, rhs: T) -> Self {
         Point::new(self.x * rhs, self.y * rhs)
     }
 }
 
-impl Sub for Point {
+impl<T: Coordinate> Div<T> for Point<T> {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, rhs: T) -> Self {
+        Point::new(self.x / rhs, self.y / rhs)
+    }
+}
+
+impl<T: Coordinate> Neg for Point<T> {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl<T: Coordinate> Sub for Point<T> {
     type Output = Self;
 
     #[inline]
@@ -142,10 +283,98 @@ impl Sub for Point {
     }
 }
 
-impl SubAssign for Point {
+impl<T: Coordinate> SubAssign for Point<T> {
     #[inline]
     fn sub_assign(&mut self, rhs: Self) {
-        self.x -= rhs.x;
-        self.y -= rhs.y;
+        *self = *self - rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::maneatingape::hash::*;
+    use std::hash::BuildHasher;
+
+    #[test]
+    fn test_operators() {
+        let a = Point64::new(6, -4);
+        let b = Point64::new(-1, 2);
+        assert_eq!(a + b, Point64::new(5, -2));
+        assert_eq!(a - b, Point64::new(7, -6));
+        assert_eq!(a * 3, Point64::new(18, -12));
+        assert_eq!(a / 2, Point64::new(3, -2));
+        assert_eq!(-a, Point64::new(-6, 4));
+        assert_eq!(a.manhattan(b), 13);
+        assert_eq!(a.chebyshev(b), 7);
+        assert_eq!(a.signum(b), Point64::new(1, -1));
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+    }
+
+    #[test]
+    fn test_rotation() {
+        let center = Point::new(2, 3);
+        let point = Point::new(5, 3);
+        assert_eq!(point.clockwise_around(center), Point::new(2, 6));
+        assert_eq!(point.counter_clockwise_around(center), Point::new(2, 0));
+        assert_eq!(point.clockwise_around(center).counter_clockwise_around(center), point);
+
+        let full_turn = (0..4).fold(point, |p, _| p.clockwise_around(center));
+        assert_eq!(full_turn, point);
+        assert_eq!(UP.clockwise(), RIGHT);
+        assert_eq!(UP.counter_clockwise(), LEFT);
+    }
+
+    #[test]
+    fn test_checked_and_wrapping() {
+        let max = Point::new(i32::MAX, 0);
+        assert_eq!(max.checked_add(RIGHT), None);
+        assert_eq!(max.checked_add(LEFT), Some(Point::new(i32::MAX - 1, 0)));
+        assert_eq!(Point::new(i32::MIN, 0).checked_sub(RIGHT), None);
+        assert_eq!(max.checked_mul(2), None);
+        assert_eq!(max.wrapping_add(RIGHT), Point::new(i32::MIN, 0));
+        assert_eq!(Point::new(i32::MIN, 0).wrapping_sub(RIGHT), max);
+        assert_eq!(max.wrapping_mul(2), Point::new(-2, 0));
+
+        let big = Point128::new(i64::MAX as i128, i64::MIN as i128);
+        assert_eq!(
+            big.checked_mul(4),
+            Some(Point128::new(i64::MAX as i128 * 4, i64::MIN as i128 * 4))
+        );
+        assert_eq!(Point128::new(i128::MAX, 0).checked_add(Point128::new(1, 0)), None);
+    }
+
+    #[test]
+    fn test_ordering() {
+        let mut points =
+            vec![Point::new(1, 1), Point::new(0, 2), Point::new(2, 0), Point::new(0, 1)];
+        points.sort_unstable();
+        assert_eq!(
+            points,
+            [Point::new(2, 0), Point::new(0, 1), Point::new(1, 1), Point::new(0, 2)]
+        );
+        assert!(DIAGONAL.is_sorted());
+    }
+
+    #[test]
+    fn test_hash() {
+        let hasher = BuildFxHasher;
+        let hash = |point: Point64| hasher.hash_one(point);
+        assert_eq!(hash(Point64::new(3, -7)), hash(Point64::new(3, -7)));
+        assert_ne!(hash(Point64::new(3, -7)), hash(Point64::new(-7, 3)));
+
+        let mut set = FastSet::new();
+        for y in -20..20 {
+            for x in -20..20 {
+                set.insert(Point::new(x, y));
+                set.insert(Point::new(x, y));
+            }
+        }
+        assert_eq!(set.len(), 1600);
+        assert!(set.contains(&Point::new(-20, 19)));
     }
 }